@@ -0,0 +1,207 @@
+//! Parsing of `#[try_from(...)]` attributes.
+
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, ExprLit, ExprUnary, Ident, Lit, LitStr, Path, Token, Type, UnOp};
+
+/// A `#[try_from(...)]` attribute placed on a type.
+pub(crate) struct ContainerAttr {
+    /// The types to convert from.
+    pub(crate) sources: Vec<Type>,
+    /// An async function validating the value before conversion.
+    pub(crate) validate: Option<Path>,
+    /// The error type of the conversion.
+    pub(crate) error: Option<Type>,
+    /// An async function resolving values which don't match any enum variant.
+    pub(crate) fallback: Option<Path>,
+}
+
+impl ContainerAttr {
+    /// Parses every `#[try_from(...)]` attribute in `attrs`.
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Vec<Self>> {
+        attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("try_from"))
+            .map(|attr| attr.parse_args())
+            .collect()
+    }
+}
+
+impl Parse for ContainerAttr {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut attr = ContainerAttr {
+            sources: Vec::new(),
+            validate: None,
+            error: None,
+            fallback: None,
+        };
+
+        let items = Punctuated::<Item, Token![,]>::parse_terminated(input)?;
+        for item in items {
+            match item {
+                Item::Source(ty) => attr.sources.push(ty),
+                Item::Validate(path) => set_once(&mut attr.validate, path, "validate")?,
+                Item::Error(ty) => set_once(&mut attr.error, ty, "error")?,
+                Item::Fallback(path) => set_once(&mut attr.fallback, path, "fallback")?,
+            }
+        }
+        Ok(attr)
+    }
+}
+
+/// A single comma-separated item in a `#[try_from(...)]` attribute.
+enum Item {
+    Source(Type),
+    Validate(Path),
+    Error(Type),
+    Fallback(Path),
+}
+
+impl Parse for Item {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        if !(input.peek(Ident) && input.peek2(Token![=])) {
+            return input.parse().map(Item::Source);
+        }
+
+        let key: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        if key == "validate" {
+            parse_path(input).map(Item::Validate)
+        } else if key == "error" {
+            input.parse().map(Item::Error)
+        } else if key == "fallback" {
+            parse_path(input).map(Item::Fallback)
+        } else {
+            Err(syn::Error::new_spanned(
+                &key,
+                format!("unknown `try_from` attribute `{}`", key),
+            ))
+        }
+    }
+}
+
+/// Parses a path, either bare or as a string literal.
+fn parse_path(input: ParseStream<'_>) -> syn::Result<Path> {
+    if input.peek(LitStr) {
+        input.parse::<LitStr>()?.parse()
+    } else {
+        input.parse()
+    }
+}
+
+fn set_once<T: quote::ToTokens>(slot: &mut Option<T>, value: T, name: &str) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(syn::Error::new_spanned(
+            value,
+            format!("duplicate `{}` attribute", name),
+        ));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+/// A `#[try_from(...)]` attribute placed on a field.
+#[derive(Default)]
+pub(crate) struct FieldAttr {
+    /// The name of the field in the source struct.
+    pub(crate) rename: Option<Ident>,
+    /// Don't convert this field, but use its `Default` value instead.
+    pub(crate) skip: bool,
+    /// Convert using `std::convert::Into` rather than async `TryInto`.
+    pub(crate) into: bool,
+    /// An async function used to convert the field.
+    pub(crate) with: Option<Path>,
+}
+
+impl FieldAttr {
+    /// Parses and merges every `#[try_from(...)]` attribute in `attrs`.
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut field = FieldAttr::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("try_from")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    set_once(&mut field.rename, name.parse()?, "rename")
+                } else if meta.path.is_ident("with") {
+                    let path = parse_path(meta.value()?)?;
+                    set_once(&mut field.with, path, "with")
+                } else if meta.path.is_ident("skip") {
+                    field.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("into") {
+                    field.into = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown `try_from` field attribute"))
+                }
+            })?;
+        }
+
+        if field.skip && (field.rename.is_some() || field.with.is_some() || field.into) {
+            return Err(syn::Error::new_spanned(
+                &attrs[0],
+                "`skip` can't be combined with other `try_from` attributes",
+            ));
+        }
+        if field.into && field.with.is_some() {
+            return Err(syn::Error::new_spanned(
+                &attrs[0],
+                "`into` can't be combined with `with`",
+            ));
+        }
+        Ok(field)
+    }
+}
+
+/// A `#[try_from(...)]` attribute placed on an enum variant.
+#[derive(Default)]
+pub(crate) struct VariantAttr {
+    /// The integer value of the variant.
+    pub(crate) value: Option<i128>,
+    /// Additional strings the variant can be parsed from.
+    pub(crate) aliases: Vec<LitStr>,
+}
+
+impl VariantAttr {
+    /// Parses and merges every `#[try_from(...)]` attribute in `attrs`.
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut variant = VariantAttr::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("try_from")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("value") {
+                    if variant.value.is_some() {
+                        return Err(meta.error("duplicate `value` attribute"));
+                    }
+                    let expr: Expr = meta.value()?.parse()?;
+                    variant.value = Some(int_value(&expr)?);
+                    Ok(())
+                } else if meta.path.is_ident("alias") {
+                    variant.aliases.push(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown `try_from` variant attribute"))
+                }
+            })?;
+        }
+        Ok(variant)
+    }
+}
+
+/// Evaluates an integer literal, optionally negated.
+pub(crate) fn int_value(expr: &Expr) -> syn::Result<i128> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => int_value(expr).map(|value| -value),
+        Expr::Group(group) => int_value(&group.expr),
+        _ => Err(syn::Error::new_spanned(
+            expr,
+            "expected an integer literal, use `#[try_from(value = ...)]` instead",
+        )),
+    }
+}
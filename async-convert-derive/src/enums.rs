@@ -0,0 +1,185 @@
+//! `TryFrom` for fieldless enums, from integers and strings.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{DataEnum, DeriveInput, Fields, Type};
+
+use crate::attr::{int_value, ContainerAttr, VariantAttr};
+
+/// The source types used when none are listed.
+const DEFAULT_SOURCES: &[&str] = &["u8", "u16", "u32", "i64", "String", "&str"];
+
+/// The integer types enums can be converted from.
+const INTEGERS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize",
+];
+
+/// How values of a source type are matched against variants.
+enum Kind {
+    Int,
+    Str,
+}
+
+pub(crate) fn expand(input: &DeriveInput, data: &DataEnum) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let name = ident.to_string();
+
+    // Every attribute contributes source types; options may only be set once.
+    let mut sources = Vec::new();
+    let mut error = None;
+    let mut fallback = None;
+    for attr in ContainerAttr::from_attrs(&input.attrs)? {
+        if let Some(validate) = attr.validate {
+            return Err(syn::Error::new_spanned(
+                validate,
+                "`validate` is only supported on newtype structs",
+            ));
+        }
+        if let Some(ty) = attr.error {
+            if error.replace(ty).is_some() {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "duplicate `error` attribute",
+                ));
+            }
+        }
+        if let Some(path) = attr.fallback {
+            if fallback.replace(path).is_some() {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "duplicate `fallback` attribute",
+                ));
+            }
+        }
+        sources.extend(attr.sources);
+    }
+    if sources.is_empty() {
+        for source in DEFAULT_SOURCES {
+            sources.push(syn::parse_str::<Type>(source)?);
+        }
+    }
+
+    // Collect the integer value and names of every variant.
+    let mut int_arms = Vec::new();
+    let mut str_arms = Vec::new();
+    // The discriminant of the next variant without an explicit one. It's only
+    // an error if it's needed, as overridden values don't depend on it.
+    let mut next = Ok(0_i128);
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`TryFrom` can only be derived for enums without fields",
+            ));
+        }
+
+        let attr = VariantAttr::from_attrs(&variant.attrs)?;
+        let discriminant = match &variant.discriminant {
+            Some((_, expr)) => int_value(expr),
+            None => next.clone(),
+        };
+        next = discriminant.clone().map(|discriminant| discriminant + 1);
+        let value = match attr.value {
+            Some(value) => value,
+            None => discriminant?,
+        };
+
+        let variant_ident = &variant.ident;
+        let variant_name = variant_ident.to_string();
+        let aliases = &attr.aliases;
+        int_arms.push(quote! {
+            #value => return ::core::result::Result::Ok(Self::#variant_ident),
+        });
+        str_arms.push(quote! {
+            #variant_name #(| #aliases)* => return ::core::result::Result::Ok(Self::#variant_ident),
+        });
+    }
+
+    let error_ty = match &error {
+        Some(error) => quote!(#error),
+        None => quote!(::async_convert::UnknownVariant),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut impls = TokenStream::new();
+    for source in &sources {
+        let (key, lookup) = match kind(source)? {
+            Kind::Int => (
+                quote!(::async_convert::VariantKey::Int(value as i128)),
+                quote! {
+                    match value as i128 {
+                        #(#int_arms)*
+                        _ => {}
+                    }
+                },
+            ),
+            Kind::Str => (
+                quote!(::async_convert::VariantKey::Str(
+                    ::std::string::String::from(value)
+                )),
+                quote! {
+                    match ::core::convert::AsRef::<str>::as_ref(&value) {
+                        #(#str_arms)*
+                        _ => {}
+                    }
+                },
+            ),
+        };
+
+        let unknown = match &fallback {
+            Some(fallback) => quote!(#fallback(#key).await),
+            None => quote! {
+                ::core::result::Result::Err(::core::convert::From::from(
+                    ::async_convert::UnknownVariant::new(#name, #key),
+                ))
+            },
+        };
+
+        impls.extend(quote! {
+            impl #impl_generics ::async_convert::TryFrom<#source> for #ident #ty_generics #where_clause {
+                type Error = #error_ty;
+
+                fn try_from(
+                    value: #source,
+                ) -> impl ::core::future::Future<Output = ::core::result::Result<Self, Self::Error>> + ::core::marker::Send {
+                    async move {
+                        #lookup
+                        #unknown
+                    }
+                }
+            }
+        });
+    }
+    Ok(impls)
+}
+
+/// Determines how values of a source type are matched.
+fn kind(source: &Type) -> syn::Result<Kind> {
+    let unsupported = || {
+        syn::Error::new_spanned(
+            source,
+            format!(
+                "enums can only be converted from integers, `String`, and `&str`, not `{}`",
+                source.to_token_stream()
+            ),
+        )
+    };
+
+    match source {
+        Type::Reference(reference) => match &*reference.elem {
+            Type::Path(path) if path.path.is_ident("str") => Ok(Kind::Str),
+            _ => Err(unsupported()),
+        },
+        Type::Path(path) => {
+            let ident = path.path.get_ident().ok_or_else(unsupported)?;
+            if ident == "String" {
+                Ok(Kind::Str)
+            } else if INTEGERS.iter().any(|int| ident == int) {
+                Ok(Kind::Int)
+            } else {
+                Err(unsupported())
+            }
+        }
+        _ => Err(unsupported()),
+    }
+}
@@ -0,0 +1,90 @@
+//! Field-wise `TryFrom` between structs with named fields.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, FieldsNamed};
+
+use crate::attr::{ContainerAttr, FieldAttr};
+
+pub(crate) fn expand(input: &DeriveInput, fields: &FieldsNamed) -> syn::Result<TokenStream> {
+    let attrs = ContainerAttr::from_attrs(&input.attrs)?;
+    if attrs.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "missing `#[try_from(Type)]` attribute",
+        ));
+    }
+
+    // Move every field out of the source, and convert it into the matching
+    // field of `Self`.
+    let mut conversions = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().expect("named field");
+        let attr = FieldAttr::from_attrs(&field.attrs)?;
+        if attr.skip {
+            conversions.push(quote! {
+                #ident: ::core::default::Default::default()
+            });
+            continue;
+        }
+
+        let source = attr.rename.unwrap_or_else(|| ident.clone());
+        let value = quote!(value.#source);
+        let name = ident.to_string();
+        let convert = match (&attr.with, attr.into) {
+            (Some(with), _) => quote!(#with(#value).await),
+            (None, true) => quote! {
+                ::core::result::Result::<_, ::core::convert::Infallible>::Ok(
+                    ::core::convert::Into::into(#value)
+                )
+            },
+            (None, false) => quote!(::async_convert::TryInto::try_into(#value).await),
+        };
+        conversions.push(quote! {
+            #ident: #convert.map_err(|err| ::async_convert::FieldError::new(#name, err))?
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut impls = TokenStream::new();
+    for attr in attrs {
+        if let Some(validate) = &attr.validate {
+            return Err(syn::Error::new_spanned(
+                validate,
+                "`validate` is only supported on newtype structs",
+            ));
+        }
+        if let Some(fallback) = &attr.fallback {
+            return Err(syn::Error::new_spanned(
+                fallback,
+                "`fallback` is only supported on enums",
+            ));
+        }
+        let error = match &attr.error {
+            Some(error) => quote!(#error),
+            None => quote!(::async_convert::FieldError),
+        };
+
+        for source in &attr.sources {
+            impls.extend(quote! {
+                impl #impl_generics ::async_convert::TryFrom<#source> for #ident #ty_generics #where_clause {
+                    type Error = #error;
+
+                    fn try_from(
+                        value: #source,
+                    ) -> impl ::core::future::Future<Output = ::core::result::Result<Self, Self::Error>> + ::core::marker::Send {
+                        async move {
+                            // `?` converts the `FieldError`s into `Self::Error`.
+                            ::core::result::Result::<Self, Self::Error>::Ok(Self {
+                                #(#conversions,)*
+                            })
+                        }
+                    }
+                }
+            });
+        }
+    }
+    Ok(impls)
+}
@@ -0,0 +1,148 @@
+//! Derive macros for [`async-convert`](https://docs.rs/async-convert).
+//!
+//! This crate is not meant to be used directly; enable the `derive` feature of
+//! `async-convert` instead, which re-exports the macros.
+
+#![forbid(unsafe_code, future_incompatible, rust_2018_idioms)]
+#![deny(missing_debug_implementations, nonstandard_style)]
+#![warn(missing_docs, unreachable_pub)]
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+mod attr;
+mod enums;
+mod fields;
+mod newtype;
+
+/// Derives `async_convert::TryFrom`.
+///
+/// # Newtype structs
+///
+/// `#[try_from(Type)]` generates a `TryFrom<Type>` impl which wraps the value
+/// using the field's [`From`] impl. Multiple types may be listed, and the
+/// attribute may be repeated.
+///
+/// - `validate = "path"`: an async function with the signature
+///   `async fn(&Type) -> Result<(), E>` which is awaited before the value is
+///   wrapped. The error is converted using `?`.
+/// - `error = Type`: the error type of the conversion. Required when
+///   `validate` is set, defaults to `Infallible` otherwise.
+///
+/// ```ignore
+/// use async_convert::TryFrom;
+///
+/// #[derive(Debug)]
+/// struct NotPositive;
+///
+/// async fn check_positive(value: &i32) -> Result<(), NotPositive> {
+///     if *value > 0 { Ok(()) } else { Err(NotPositive) }
+/// }
+///
+/// #[derive(TryFrom)]
+/// #[try_from(i32, validate = "check_positive", error = NotPositive)]
+/// struct GreaterThanZero(i32);
+/// ```
+///
+/// # Structs with named fields
+///
+/// `#[try_from(Type)]` generates a `TryFrom<Type>` impl which converts every
+/// field of `Type` into the field of the same name using async `TryInto`. The
+/// error type defaults to `async_convert::FieldError`, which records the name
+/// of the field that failed. The container accepts these options:
+///
+/// - `error = Type`: use a different error type, which must implement
+///   `From<FieldError>`.
+///
+/// Fields accept these options:
+///
+/// - `rename = "name"`: read the value from a differently named source field.
+/// - `skip`: don't read from the source, use `Default::default()` instead.
+/// - `into`: convert using `std::convert::Into`, e.g. when both fields have the
+///   same type.
+/// - `with = "path"`: convert using an async function with the signature
+///   `async fn(Source) -> Result<Field, E>`.
+///
+/// Field errors must be convertible into
+/// `Box<dyn std::error::Error + Send + Sync>`.
+///
+/// ```ignore
+/// use async_convert::TryFrom;
+///
+/// struct UserDto {
+///     id: u64,
+///     user_name: String,
+///     email: String,
+/// }
+///
+/// #[derive(TryFrom)]
+/// #[try_from(UserDto)]
+/// struct User {
+///     id: UserId,
+///     #[try_from(rename = "user_name", into)]
+///     name: String,
+///     #[try_from(with = "parse_email")]
+///     email: Email,
+///     #[try_from(skip)]
+///     sessions: Vec<Session>,
+/// }
+/// ```
+///
+/// # Enums
+///
+/// Enums without fields can be converted from integers, `String`, and `&str`.
+/// Integers match the variant's value, which is its discriminant unless set
+/// otherwise. Strings match the variant's name or any of its aliases. The
+/// container accepts these options:
+///
+/// - A list of source types, defaulting to `u8`, `u16`, `u32`, `i64`,
+///   `String`, and `&str`.
+/// - `fallback = "path"`: an async function with the signature
+///   `async fn(VariantKey) -> Result<Self, E>` which is awaited for values that
+///   don't match any variant.
+/// - `error = Type`: the error type of the conversion. Without a fallback it
+///   must implement `From<UnknownVariant>`. Defaults to
+///   `async_convert::UnknownVariant`.
+///
+/// Variants accept these options:
+///
+/// - `value = 3`: the integer value of the variant.
+/// - `alias = "name"`: an additional string to match. May be repeated.
+///
+/// ```ignore
+/// use async_convert::TryFrom;
+///
+/// #[derive(TryFrom)]
+/// #[try_from(u8, &str)]
+/// enum Region {
+///     #[try_from(alias = "us-east")]
+///     UsEast = 1,
+///     #[try_from(value = 4, alias = "eu-west")]
+///     EuWest,
+/// }
+/// ```
+#[proc_macro_derive(TryFrom, attributes(try_from))]
+pub fn derive_try_from(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => newtype::expand(input),
+            Fields::Named(fields) => fields::expand(input, fields),
+            _ => Err(syn::Error::new_spanned(
+                &input.ident,
+                "`TryFrom` can only be derived for newtype structs, structs with named fields, and enums",
+            )),
+        },
+        Data::Enum(data) => enums::expand(input, data),
+        Data::Union(_) => Err(syn::Error::new_spanned(
+            &input.ident,
+            "`TryFrom` can't be derived for unions",
+        )),
+    }
+}
@@ -0,0 +1,72 @@
+//! `TryFrom` for newtype structs, optionally guarded by an async validator.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+use crate::attr::ContainerAttr;
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let attrs = ContainerAttr::from_attrs(&input.attrs)?;
+    if attrs.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "missing `#[try_from(Type)]` attribute",
+        ));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut impls = TokenStream::new();
+    for attr in attrs {
+        if attr.sources.is_empty() {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "missing source type in `#[try_from(...)]`",
+            ));
+        }
+
+        if let Some(fallback) = &attr.fallback {
+            return Err(syn::Error::new_spanned(
+                fallback,
+                "`fallback` is only supported on enums",
+            ));
+        }
+
+        let error = match (&attr.validate, &attr.error) {
+            (_, Some(error)) => quote!(#error),
+            (None, None) => quote!(::core::convert::Infallible),
+            (Some(validate), None) => {
+                return Err(syn::Error::new_spanned(
+                    validate,
+                    "`validate` requires an `error` type",
+                ))
+            }
+        };
+
+        let validate = attr.validate.as_ref().map(|validate| {
+            quote! {
+                #validate(&value).await?;
+            }
+        });
+
+        for source in &attr.sources {
+            impls.extend(quote! {
+                impl #impl_generics ::async_convert::TryFrom<#source> for #ident #ty_generics #where_clause {
+                    type Error = #error;
+
+                    fn try_from(
+                        value: #source,
+                    ) -> impl ::core::future::Future<Output = ::core::result::Result<Self, Self::Error>> + ::core::marker::Send {
+                        async move {
+                            #validate
+                            ::core::result::Result::Ok(Self(::core::convert::From::from(value)))
+                        }
+                    }
+                }
+            });
+        }
+    }
+    Ok(impls)
+}
@@ -0,0 +1,246 @@
+use async_convert::TryFrom;
+use futures_lite::future::block_on;
+
+mod newtype {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct NotPositive;
+
+    async fn check_positive(value: &i32) -> Result<(), NotPositive> {
+        if *value > 0 {
+            Ok(())
+        } else {
+            Err(NotPositive)
+        }
+    }
+
+    #[derive(Debug, PartialEq, TryFrom)]
+    #[try_from(i32, validate = "check_positive", error = NotPositive)]
+    struct GreaterThanZero(i32);
+
+    #[derive(Debug, PartialEq, TryFrom)]
+    #[try_from(u8, u16)]
+    struct Wide(u32);
+
+    #[test]
+    fn validates() {
+        block_on(async {
+            assert_eq!(GreaterThanZero::try_from(1).await, Ok(GreaterThanZero(1)));
+            assert_eq!(GreaterThanZero::try_from(0).await, Err(NotPositive));
+        });
+    }
+
+    #[test]
+    fn without_validator() {
+        block_on(async {
+            assert_eq!(Wide::try_from(3_u8).await, Ok(Wide(3)));
+            assert_eq!(Wide::try_from(300_u16).await, Ok(Wide(300)));
+        });
+    }
+}
+
+mod fields {
+    use super::*;
+    use async_convert::FieldError;
+
+    // no conversion exists from this type, so the derive must ignore fields
+    // of this type which don't exist on the target.
+    struct Unconvertible;
+
+    struct UserDto {
+        id: i32,
+        user_name: String,
+        email: String,
+        #[allow(dead_code)]
+        unused: Unconvertible,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct UserId(i32);
+
+    impl TryFrom<i32> for UserId {
+        type Error = &'static str;
+
+        async fn try_from(value: i32) -> Result<Self, Self::Error> {
+            if value < 0 {
+                Err("negative id")
+            } else {
+                Ok(UserId(value))
+            }
+        }
+    }
+
+    async fn parse_email(email: String) -> Result<String, &'static str> {
+        if email.contains('@') {
+            Ok(email)
+        } else {
+            Err("invalid email")
+        }
+    }
+
+    #[derive(Debug, PartialEq, TryFrom)]
+    #[try_from(UserDto)]
+    struct User {
+        id: UserId,
+        #[try_from(rename = "user_name", into)]
+        name: String,
+        #[try_from(with = "parse_email")]
+        email: String,
+        #[try_from(skip)]
+        sessions: Vec<u32>,
+    }
+
+    #[derive(Debug)]
+    struct InvalidUser(FieldError);
+
+    impl From<FieldError> for InvalidUser {
+        fn from(err: FieldError) -> Self {
+            InvalidUser(err)
+        }
+    }
+
+    #[derive(Debug, PartialEq, TryFrom)]
+    #[try_from(UserDto, error = InvalidUser)]
+    struct Account {
+        id: UserId,
+    }
+
+    #[derive(Debug, PartialEq, TryFrom)]
+    #[try_from(UserDto, error = async_convert::ConversionError)]
+    struct Profile {
+        #[try_from(with = "parse_email")]
+        email: String,
+    }
+
+    fn dto(id: i32, email: &str) -> UserDto {
+        UserDto {
+            id,
+            user_name: "chashu".into(),
+            email: email.into(),
+            unused: Unconvertible,
+        }
+    }
+
+    #[test]
+    fn converts_every_field() {
+        block_on(async {
+            let user = User::try_from(dto(1, "chashu@example.com")).await.unwrap();
+            assert_eq!(
+                user,
+                User {
+                    id: UserId(1),
+                    name: "chashu".into(),
+                    email: "chashu@example.com".into(),
+                    sessions: vec![],
+                }
+            );
+        });
+    }
+
+    #[test]
+    fn records_failed_field() {
+        block_on(async {
+            let err: FieldError = User::try_from(dto(-1, "chashu@example.com"))
+                .await
+                .unwrap_err();
+            assert_eq!(err.field(), "id");
+
+            let err = User::try_from(dto(1, "chashu")).await.unwrap_err();
+            assert_eq!(err.field(), "email");
+            assert_eq!(err.into_source().to_string(), "invalid email");
+        });
+    }
+
+    #[test]
+    fn custom_error() {
+        block_on(async {
+            let account = Account::try_from(dto(1, "chashu")).await.unwrap();
+            assert_eq!(account, Account { id: UserId(1) });
+            let InvalidUser(err) = Account::try_from(dto(-1, "chashu")).await.unwrap_err();
+            assert_eq!(err.field(), "id");
+
+            let err = Profile::try_from(dto(1, "chashu")).await.unwrap_err();
+            assert_eq!(err.field_path(), Some("email"));
+        });
+    }
+}
+
+mod enums {
+    use super::*;
+    use async_convert::{UnknownVariant, VariantKey};
+
+    #[derive(Debug, PartialEq, TryFrom)]
+    enum Color {
+        Red,
+        #[try_from(alias = "verde")]
+        Green = 3,
+        Blue,
+        #[try_from(value = 10)]
+        Black,
+        White,
+    }
+
+    #[derive(Debug, PartialEq, TryFrom)]
+    #[try_from(u16, String, fallback = "resolve_region")]
+    enum Region {
+        #[try_from(alias = "us-east")]
+        UsEast,
+        EuWest,
+    }
+
+    // pretend we're looking this up in a remote catalog.
+    async fn resolve_region(key: VariantKey) -> Result<Region, UnknownVariant> {
+        match key {
+            VariantKey::Int(42) => Ok(Region::EuWest),
+            VariantKey::Str(s) if s == "eu-west" => Ok(Region::EuWest),
+            key => Err(UnknownVariant::new("Region", key)),
+        }
+    }
+
+    #[test]
+    fn from_integers() {
+        block_on(async {
+            assert_eq!(Color::try_from(0_u8).await, Ok(Color::Red));
+            assert_eq!(Color::try_from(3_u32).await, Ok(Color::Green));
+            assert_eq!(Color::try_from(4_i64).await, Ok(Color::Blue));
+            assert_eq!(Color::try_from(10_u16).await, Ok(Color::Black));
+            assert_eq!(Color::White as u8, 6);
+            assert_eq!(Color::try_from(6_u8).await, Ok(Color::White));
+            assert!(Color::try_from(5_u8).await.is_err());
+            assert!(Color::try_from(11_u8).await.is_err());
+
+            let err = Color::try_from(1_u8).await.unwrap_err();
+            assert_eq!(err.key(), &VariantKey::Int(1));
+            assert_eq!(err.enum_name(), "Color");
+        });
+    }
+
+    #[test]
+    fn from_strings() {
+        block_on(async {
+            assert_eq!(Color::try_from("Red").await, Ok(Color::Red));
+            assert_eq!(Color::try_from("verde").await, Ok(Color::Green));
+            assert_eq!(Color::try_from(String::from("Blue")).await, Ok(Color::Blue));
+            assert!(Color::try_from("red").await.is_err());
+        });
+    }
+
+    #[test]
+    fn fallback() {
+        block_on(async {
+            assert_eq!(
+                Region::try_from(String::from("us-east")).await,
+                Ok(Region::UsEast)
+            );
+            assert_eq!(Region::try_from(1_u16).await, Ok(Region::EuWest));
+            assert_eq!(Region::try_from(42_u16).await, Ok(Region::EuWest));
+            assert_eq!(
+                Region::try_from(String::from("eu-west")).await,
+                Ok(Region::EuWest)
+            );
+            let err = Region::try_from(String::from("mars")).await.unwrap_err();
+            assert_eq!(err.key(), &VariantKey::Str("mars".into()));
+        });
+    }
+}
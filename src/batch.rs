@@ -0,0 +1,417 @@
+use core::future::{self, Future};
+use core::mem;
+use core::ops::ControlFlow;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, Waker};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::hash::{BuildHasher, Hash};
+use std::sync::{Arc, Mutex};
+use std::task::Wake;
+
+use crate::TryFrom;
+
+/// Converts every element of a collection concurrently.
+///
+/// Conversions are started in order, and at most [`Batch::limit`] conversions
+/// run at the same time. The output preserves the order of the input: for
+/// maps, every value stays associated with its key.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{Batch, TryFrom};
+/// # futures_lite::future::block_on(async {
+///
+/// struct Body(String);
+///
+/// impl TryFrom<&'static str> for Body {
+///     type Error = &'static str;
+///
+///     async fn try_from(request: &'static str) -> Result<Self, Self::Error> {
+///         // pretend we're reading the request body here instead.
+///         Ok(Body(request.to_uppercase()))
+///     }
+/// }
+///
+/// let requests = vec!["a", "b", "c"];
+/// let bodies: Vec<Body> = Batch::new(requests).limit(2).try_convert().await?;
+/// assert_eq!(bodies[2].0, "C");
+/// # Ok::<(), &'static str>(()) });
+/// ```
+#[derive(Debug)]
+#[must_use = "batches do nothing unless converted"]
+pub struct Batch<C> {
+    items: C,
+    limit: usize,
+}
+
+impl<C: Collection> Batch<C> {
+    /// Create a new instance.
+    ///
+    /// By default all conversions run at the same time.
+    pub fn new(items: C) -> Self {
+        Self {
+            items,
+            limit: usize::MAX,
+        }
+    }
+
+    /// Sets the maximum number of conversions which run at the same time.
+    ///
+    /// A value of `0` is treated as `1`.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit.max(1);
+        self
+    }
+
+    /// Converts every element into `U`, failing on the first error.
+    ///
+    /// Once a conversion fails no new conversions are started, and the ones
+    /// still running are dropped.
+    pub async fn try_convert<U>(self) -> Result<C::Output<U>, U::Error>
+    where
+        U: TryFrom<C::Item>,
+    {
+        let (keys, items) = self.items.split();
+        let mut outputs: Vec<Option<U>> = items.iter().map(|_| None).collect();
+        let mut error = None;
+        for_each_concurrent(items, self.limit, |index, res| match res {
+            Ok(value) => {
+                outputs[index] = Some(value);
+                ControlFlow::Continue(())
+            }
+            Err(err) => {
+                error = Some(err);
+                ControlFlow::Break(())
+            }
+        })
+        .await;
+
+        match error {
+            Some(err) => Err(err),
+            None => {
+                let outputs = outputs
+                    .into_iter()
+                    .map(|value| value.expect("every conversion completed successfully"));
+                Ok(C::join(keys, outputs.collect()))
+            }
+        }
+    }
+}
+
+impl<C: Collection> Batch<C> {
+    /// Converts every element into `U`, reporting both the elements which
+    /// were converted and the ones which failed.
+    ///
+    /// Elements are identified by their position in the collection. For maps
+    /// this is the position in the map's iteration order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_convert::{Batch, BatchMode, TryFrom};
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// struct Record(u32);
+    ///
+    /// impl TryFrom<i64> for Record {
+    ///     type Error = String;
+    ///
+    ///     async fn try_from(value: i64) -> Result<Self, Self::Error> {
+    ///         std::convert::TryFrom::try_from(value)
+    ///             .map(Record)
+    ///             .map_err(|_| format!("{} is out of range", value))
+    ///     }
+    /// }
+    ///
+    /// let report = Batch::new(vec![1, -2, 3, -4])
+    ///     .try_convert_partial::<Record>(BatchMode::CollectAll)
+    ///     .await;
+    /// assert_eq!(report.converted().len(), 2);
+    /// assert_eq!(report.failed()[0], (1, "-2 is out of range".to_string()));
+    /// assert_eq!(report.failed()[1].0, 3);
+    /// # });
+    /// ```
+    pub async fn try_convert_partial<U>(self, mode: BatchMode) -> PartialConversion<U, U::Error>
+    where
+        U: TryFrom<C::Item>,
+    {
+        let (_, items) = self.items.split();
+        let mut converted = Vec::with_capacity(items.len());
+        let mut failed = Vec::new();
+        for_each_concurrent(items, self.limit, |index, res| match res {
+            Ok(value) => {
+                converted.push((index, value));
+                ControlFlow::Continue(())
+            }
+            Err(err) => match mode {
+                BatchMode::FailFast => {
+                    failed.push((index, err));
+                    ControlFlow::Break(())
+                }
+                BatchMode::CollectAll => {
+                    failed.push((index, err));
+                    ControlFlow::Continue(())
+                }
+                BatchMode::BestEffort => ControlFlow::Continue(()),
+            },
+        })
+        .await;
+
+        converted.sort_by_key(|(index, _)| *index);
+        failed.sort_by_key(|(index, _)| *index);
+        PartialConversion { converted, failed }
+    }
+}
+
+/// How [`Batch::try_convert_partial`] handles failed conversions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BatchMode {
+    /// Stop on the first error. The report contains the conversions which
+    /// completed before, and the error.
+    FailFast,
+    /// Convert every element, collecting all errors.
+    CollectAll,
+    /// Convert every element, dropping all errors.
+    BestEffort,
+}
+
+/// The outcome of converting a batch, created by
+/// [`Batch::try_convert_partial`].
+///
+/// Both the converted values and the errors are paired with the position of
+/// their element in the input, and sorted by it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialConversion<U, E> {
+    converted: Vec<(usize, U)>,
+    failed: Vec<(usize, E)>,
+}
+
+impl<U, E> PartialConversion<U, E> {
+    /// The values which were converted, paired with their position.
+    pub fn converted(&self) -> &[(usize, U)] {
+        &self.converted
+    }
+
+    /// The errors of the conversions which failed, paired with their position.
+    pub fn failed(&self) -> &[(usize, E)] {
+        &self.failed
+    }
+
+    /// Returns `true` if no errors were recorded.
+    pub fn is_ok(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// Consumes the report, returning the converted values paired with their
+    /// position, and dropping the errors.
+    pub fn into_converted(self) -> Vec<(usize, U)> {
+        self.converted
+    }
+
+    /// Consumes the report, returning the converted values if no errors were
+    /// recorded, or the errors otherwise.
+    pub fn into_result(self) -> Result<Vec<U>, Vec<(usize, E)>> {
+        match self.failed.is_empty() {
+            true => Ok(self.converted.into_iter().map(|(_, value)| value).collect()),
+            false => Err(self.failed),
+        }
+    }
+}
+
+/// Runs the conversions of `items` concurrently, calling `f` with the index
+/// and output of every conversion as it completes.
+///
+/// Every conversion gets its own waker, so a wake only re-polls the
+/// conversion it belongs to. No new conversions are started once `f` returns
+/// `ControlFlow::Break`.
+async fn for_each_concurrent<T, U, F>(items: Vec<T>, limit: usize, mut f: F)
+where
+    U: TryFrom<T>,
+    F: FnMut(usize, Result<U, U::Error>) -> ControlFlow<()>,
+{
+    let mut pending = items.into_iter().enumerate();
+    let queue = Arc::new(ReadyQueue::default());
+    // Running conversions are stored in slots, which are reused once free.
+    let mut slots = Vec::new();
+    let mut free = Vec::new();
+    let mut running = 0;
+    future::poll_fn(|cx| loop {
+        *queue.waker.lock().unwrap_or_else(|err| err.into_inner()) = Some(cx.waker().clone());
+        while running < limit {
+            let Some((index, item)) = pending.next() else {
+                break;
+            };
+            let slot = free.pop().unwrap_or(slots.len());
+            let waker = Arc::new(SlotWaker {
+                slot,
+                queued: AtomicBool::new(true),
+                queue: queue.clone(),
+            });
+            let conversion = (index, Box::pin(U::try_from(item)), waker);
+            match slots.get_mut(slot) {
+                Some(entry) => *entry = Some(conversion),
+                None => slots.push(Some(conversion)),
+            }
+            running += 1;
+            // New conversions are polled right away.
+            queue
+                .ready
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .push(slot);
+        }
+        if running == 0 {
+            return Poll::Ready(());
+        }
+
+        // Poll the conversions which were woken, and start new ones if any
+        // completed.
+        let ready = mem::take(&mut *queue.ready.lock().unwrap_or_else(|err| err.into_inner()));
+        let mut completed = false;
+        for slot in ready {
+            // Wakers of completed conversions may still wake their old slot.
+            let Some((_, conversion, waker)) = &mut slots[slot] else {
+                continue;
+            };
+            waker.queued.store(false, Ordering::Release);
+            let waker = Waker::from(waker.clone());
+            if let Poll::Ready(res) = conversion.as_mut().poll(&mut Context::from_waker(&waker)) {
+                let (index, _, _) = slots[slot].take().expect("slot is running");
+                free.push(slot);
+                running -= 1;
+                completed = true;
+                if f(index, res).is_break() {
+                    return Poll::Ready(());
+                }
+            }
+        }
+        if !completed {
+            return Poll::Pending;
+        }
+    })
+    .await
+}
+
+/// The slots of the conversions which were woken since they were last polled.
+#[derive(Default)]
+struct ReadyQueue {
+    ready: Mutex<Vec<usize>>,
+    waker: Mutex<Option<Waker>>,
+}
+
+/// Wakes a single conversion, by queueing its slot and waking the batch.
+struct SlotWaker {
+    slot: usize,
+    queued: AtomicBool,
+    queue: Arc<ReadyQueue>,
+}
+
+impl Wake for SlotWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if self.queued.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let queue = &self.queue;
+        queue
+            .ready
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(self.slot);
+        let waker = queue
+            .waker
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// A collection whose elements can be converted by [`Batch`].
+pub trait Collection: Sized {
+    /// The type of the elements which are converted.
+    type Item;
+
+    /// The parts of the collection which are kept as-is, such as map keys.
+    type Keys;
+
+    /// The collection of converted elements.
+    type Output<U>;
+
+    /// Splits the collection into its keys and elements.
+    fn split(self) -> (Self::Keys, Vec<Self::Item>);
+
+    /// Joins the keys with the converted elements, in the same order as they
+    /// were split.
+    fn join<U>(keys: Self::Keys, items: Vec<U>) -> Self::Output<U>;
+}
+
+impl<T> Collection for Vec<T> {
+    type Item = T;
+    type Keys = ();
+    type Output<U> = Vec<U>;
+
+    fn split(self) -> (Self::Keys, Vec<Self::Item>) {
+        ((), self)
+    }
+
+    fn join<U>(_: Self::Keys, items: Vec<U>) -> Self::Output<U> {
+        items
+    }
+}
+
+impl<T> Collection for VecDeque<T> {
+    type Item = T;
+    type Keys = ();
+    type Output<U> = VecDeque<U>;
+
+    fn split(self) -> (Self::Keys, Vec<Self::Item>) {
+        ((), self.into())
+    }
+
+    fn join<U>(_: Self::Keys, items: Vec<U>) -> Self::Output<U> {
+        items.into()
+    }
+}
+
+impl<K, T, S> Collection for HashMap<K, T, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Clone,
+{
+    type Item = T;
+    type Keys = (Vec<K>, S);
+    type Output<U> = HashMap<K, U, S>;
+
+    fn split(self) -> (Self::Keys, Vec<Self::Item>) {
+        let hasher = self.hasher().clone();
+        let (keys, items) = self.into_iter().unzip();
+        ((keys, hasher), items)
+    }
+
+    fn join<U>((keys, hasher): Self::Keys, items: Vec<U>) -> Self::Output<U> {
+        let mut map = HashMap::with_capacity_and_hasher(keys.len(), hasher);
+        map.extend(keys.into_iter().zip(items));
+        map
+    }
+}
+
+impl<K: Ord, T> Collection for BTreeMap<K, T> {
+    type Item = T;
+    type Keys = Vec<K>;
+    type Output<U> = BTreeMap<K, U>;
+
+    fn split(self) -> (Self::Keys, Vec<Self::Item>) {
+        self.into_iter().unzip()
+    }
+
+    fn join<U>(keys: Self::Keys, items: Vec<U>) -> Self::Output<U> {
+        keys.into_iter().zip(items).collect()
+    }
+}
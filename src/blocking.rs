@@ -0,0 +1,113 @@
+use core::future::Future;
+
+use crate::TryFrom;
+
+#[cfg(not(any(feature = "tokio", feature = "async-std")))]
+mod pool;
+
+/// Runs synchronous [`std::convert::TryFrom`] and [`std::convert::TryInto`]
+/// conversions on a blocking thread pool.
+///
+/// This is useful for conversions which are CPU-heavy, such as decompression,
+/// image decoding, or password hashing, and shouldn't run on the executor.
+/// `Blocking<U>` implements `TryFrom<Blocking<T>>` whenever `T` implements
+/// `std::convert::TryInto<U>`. If the conversion panics, the panic is resumed
+/// when the future is awaited.
+///
+/// The thread pool is selected through cargo features:
+///
+/// - `tokio`: runs the conversion using `tokio::task::spawn_blocking`. This
+///   must be awaited from within a tokio runtime.
+/// - `async-std`: runs the conversion using `async_std::task::spawn_blocking`.
+/// - Without either feature a minimal built-in thread pool is used.
+///
+/// If both `tokio` and `async-std` are enabled, `tokio` takes precedence.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{Blocking, TryFrom};
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     #[cfg(feature = "tokio")]
+/// #     let rt = tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap();
+/// #     #[cfg(feature = "tokio")]
+/// #     return rt.block_on(future);
+/// #     #[cfg(not(feature = "tokio"))]
+/// #     return futures_lite::future::block_on(future);
+/// # }
+/// # block_on(async {
+///
+/// struct Checksum(u32);
+///
+/// impl std::convert::TryFrom<Vec<u8>> for Checksum {
+///     type Error = &'static str;
+///
+///     fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+///         if bytes.is_empty() {
+///             return Err("cannot checksum an empty buffer");
+///         }
+///         // pretend this is expensive.
+///         Ok(Checksum(bytes.iter().map(|b| *b as u32).sum()))
+///     }
+/// }
+///
+/// let Blocking(checksum) = Blocking::<Checksum>::try_from(Blocking(vec![1, 2, 3])).await?;
+/// assert_eq!(checksum.0, 6);
+/// # Ok::<(), &'static str>(()) });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Blocking<T>(pub T);
+
+impl<T> Blocking<T> {
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, U> TryFrom<Blocking<T>> for Blocking<U>
+where
+    T: core::convert::TryInto<U> + Send + 'static,
+    U: Send + 'static,
+    T::Error: Send + 'static,
+{
+    type Error = T::Error;
+
+    fn try_from(value: Blocking<T>) -> impl Future<Output = Result<Self, Self::Error>> + Send {
+        unblock(move || value.0.try_into().map(Blocking))
+    }
+}
+
+/// Runs a closure on the blocking thread pool.
+#[cfg(feature = "tokio")]
+async fn unblock<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(output) => output,
+        Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+        Err(err) => panic!("blocking conversion was cancelled: {}", err),
+    }
+}
+
+/// Runs a closure on the blocking thread pool.
+#[cfg(all(feature = "async-std", not(feature = "tokio")))]
+async fn unblock<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    async_std::task::spawn_blocking(f).await
+}
+
+/// Runs a closure on the blocking thread pool.
+#[cfg(not(any(feature = "tokio", feature = "async-std")))]
+async fn unblock<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    pool::spawn(f).await
+}
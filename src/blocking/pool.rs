@@ -0,0 +1,136 @@
+//! A minimal thread pool for running blocking conversions.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::Duration;
+
+/// The maximum number of threads the pool will spawn.
+const MAX_THREADS: usize = 64;
+
+/// How long an idle thread waits for new work before exiting.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct Pool {
+    state: Mutex<State>,
+    condvar: Condvar,
+}
+
+struct State {
+    queue: VecDeque<Job>,
+    idle: usize,
+    threads: usize,
+}
+
+impl Pool {
+    fn get() -> &'static Pool {
+        static POOL: OnceLock<Pool> = OnceLock::new();
+        POOL.get_or_init(|| Pool {
+            state: Mutex::new(State {
+                queue: VecDeque::new(),
+                idle: 0,
+                threads: 0,
+            }),
+            condvar: Condvar::new(),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn submit(&'static self, job: Job) {
+        let mut state = self.lock();
+        state.queue.push_back(job);
+        // Idle threads only decrement `idle` once they've woken up, so compare
+        // against the queue to start a thread for every job they can't take.
+        if state.queue.len() > state.idle && state.threads < MAX_THREADS {
+            state.threads += 1;
+            thread::Builder::new()
+                .name("async-convert/blocking".into())
+                .spawn(move || self.run())
+                .expect("failed to spawn a blocking thread");
+        } else {
+            self.condvar.notify_one();
+        }
+    }
+
+    fn run(&self) {
+        let mut state = self.lock();
+        loop {
+            if let Some(job) = state.queue.pop_front() {
+                drop(state);
+                job();
+                state = self.lock();
+                continue;
+            }
+
+            state.idle += 1;
+            let (next, timeout) = self
+                .condvar
+                .wait_timeout(state, IDLE_TIMEOUT)
+                .unwrap_or_else(|err| err.into_inner());
+            state = next;
+            state.idle -= 1;
+
+            if timeout.timed_out() && state.queue.is_empty() {
+                state.threads -= 1;
+                return;
+            }
+        }
+    }
+}
+
+/// Runs a closure on the pool, returning a future which resolves to its output.
+pub(super) fn spawn<F, R>(f: F) -> Task<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let slot = Arc::new(Mutex::new(Slot {
+        output: None,
+        waker: None,
+    }));
+    let task = Task { slot: slot.clone() };
+    Pool::get().submit(Box::new(move || {
+        let output = panic::catch_unwind(AssertUnwindSafe(f));
+        let mut slot = slot.lock().unwrap_or_else(|err| err.into_inner());
+        slot.output = Some(output);
+        if let Some(waker) = slot.waker.take() {
+            waker.wake();
+        }
+    }));
+    task
+}
+
+struct Slot<R> {
+    output: Option<thread::Result<R>>,
+    waker: Option<Waker>,
+}
+
+/// The output of a closure running on the pool.
+pub(super) struct Task<R> {
+    slot: Arc<Mutex<Slot<R>>>,
+}
+
+impl<R> Future for Task<R> {
+    type Output = R;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.lock().unwrap_or_else(|err| err.into_inner());
+        match slot.output.take() {
+            Some(Ok(output)) => Poll::Ready(output),
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,564 @@
+use std::any;
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// A boxed error which can be sent between threads.
+pub(crate) type BoxError = Box<dyn Error + Send + Sync + 'static>;
+
+/// An error which occurred while converting a single field of a struct.
+///
+/// This is the default error type of field-wise conversions generated by
+/// `#[derive(TryFrom)]`, and records which field failed to convert.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::FieldError;
+///
+/// let err = FieldError::new("age", "must be a positive number");
+/// assert_eq!(err.field(), "age");
+/// assert_eq!(err.to_string(), "failed to convert field `age`");
+/// ```
+#[derive(Debug)]
+pub struct FieldError {
+    field: &'static str,
+    source: BoxError,
+}
+
+impl FieldError {
+    /// Create a new instance.
+    pub fn new(field: &'static str, source: impl Into<BoxError>) -> Self {
+        Self {
+            field,
+            source: source.into(),
+        }
+    }
+
+    /// The name of the field which failed to convert.
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    /// Consumes the error, returning the error the field failed with.
+    pub fn into_source(self) -> BoxError {
+        self.source
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to convert field `{}`", self.field)
+    }
+}
+
+impl Error for FieldError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+/// The value which didn't match any enum variant.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::VariantKey;
+///
+/// assert_eq!(VariantKey::Int(3).to_string(), "3");
+/// assert_eq!(VariantKey::Str("x".into()).to_string(), "\"x\"");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VariantKey {
+    /// An integer value.
+    Int(i128),
+    /// A string value.
+    Str(String),
+}
+
+impl fmt::Display for VariantKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariantKey::Int(int) => write!(f, "{}", int),
+            VariantKey::Str(string) => write!(f, "{:?}", string),
+        }
+    }
+}
+
+/// An error returned when a value doesn't match any variant of an enum.
+///
+/// This is the default error type of enum conversions generated by
+/// `#[derive(TryFrom)]`.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{UnknownVariant, VariantKey};
+///
+/// let err = UnknownVariant::new("Region", VariantKey::Str("mars".into()));
+/// assert_eq!(err.to_string(), "unknown variant \"mars\" for enum `Region`");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownVariant {
+    enum_name: &'static str,
+    key: VariantKey,
+}
+
+impl UnknownVariant {
+    /// Create a new instance.
+    pub fn new(enum_name: &'static str, key: VariantKey) -> Self {
+        Self { enum_name, key }
+    }
+
+    /// The name of the enum.
+    pub fn enum_name(&self) -> &'static str {
+        self.enum_name
+    }
+
+    /// The value which didn't match any variant.
+    pub fn key(&self) -> &VariantKey {
+        &self.key
+    }
+}
+
+impl fmt::Display for UnknownVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown variant {} for enum `{}`",
+            self.key, self.enum_name
+        )
+    }
+}
+
+impl Error for UnknownVariant {}
+
+/// An error returned when an input is larger than the configured limit.
+///
+/// Readers fail with an [`io::Error`] of kind [`io::ErrorKind::InvalidData`]
+/// wrapping this error, which can be retrieved through
+/// [`io::Error::get_ref`].
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::LimitExceeded;
+///
+/// let err = LimitExceeded::new(1024, 4096);
+/// assert_eq!(err.read(), 4096);
+/// assert_eq!(err.to_string(), "input exceeds the limit of 1024 bytes");
+///
+/// let io_err = std::io::Error::from(err);
+/// let inner = io_err.get_ref().unwrap().downcast_ref::<LimitExceeded>();
+/// assert_eq!(inner, Some(&LimitExceeded::new(1024, 4096)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitExceeded {
+    limit: usize,
+    read: usize,
+}
+
+impl LimitExceeded {
+    /// Create a new instance.
+    pub fn new(limit: usize, read: usize) -> Self {
+        Self { limit, read }
+    }
+
+    /// The maximum number of bytes which were allowed.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// The number of bytes which had been read when the limit was exceeded.
+    pub fn read(&self) -> usize {
+        self.read
+    }
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "input exceeds the limit of {} bytes", self.limit)
+    }
+}
+
+impl Error for LimitExceeded {}
+
+impl From<LimitExceeded> for io::Error {
+    fn from(err: LimitExceeded) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
+/// An error returned when decoding a serialized value fails.
+///
+/// This is the error type of the serialization format wrappers, such as
+/// `Json`, and distinguishes failures to read the input from inputs which are
+/// too large or malformed.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{DecodeError, LimitExceeded};
+///
+/// let io_err = std::io::Error::from(LimitExceeded::new(1024, 4096));
+/// match DecodeError::from(io_err) {
+///     DecodeError::LimitExceeded(err) => assert_eq!(err.limit(), 1024),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Debug)]
+pub enum DecodeError {
+    /// Reading the input failed.
+    Io(io::Error),
+    /// The input was larger than the configured limit.
+    LimitExceeded(LimitExceeded),
+    /// The input couldn't be decoded into the target type.
+    Syntax(SyntaxError),
+    /// No decoder was registered for the content type of the input.
+    UnsupportedMediaType(UnsupportedMediaType),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Io(_) => f.write_str("failed to read input"),
+            DecodeError::LimitExceeded(err) => err.fmt(f),
+            DecodeError::Syntax(err) => err.fmt(f),
+            DecodeError::UnsupportedMediaType(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DecodeError::Io(err) => Some(err),
+            DecodeError::LimitExceeded(_) => None,
+            DecodeError::Syntax(err) => err.source(),
+            DecodeError::UnsupportedMediaType(_) => None,
+        }
+    }
+}
+
+// Readers report exceeded limits as I/O errors, so they're unwrapped here.
+impl From<io::Error> for DecodeError {
+    fn from(err: io::Error) -> Self {
+        let limit = err
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<LimitExceeded>());
+        match limit {
+            Some(limit) => DecodeError::LimitExceeded(*limit),
+            None => DecodeError::Io(err),
+        }
+    }
+}
+
+impl From<LimitExceeded> for DecodeError {
+    fn from(err: LimitExceeded) -> Self {
+        DecodeError::LimitExceeded(err)
+    }
+}
+
+impl From<SyntaxError> for DecodeError {
+    fn from(err: SyntaxError) -> Self {
+        DecodeError::Syntax(err)
+    }
+}
+
+impl From<UnsupportedMediaType> for DecodeError {
+    fn from(err: UnsupportedMediaType) -> Self {
+        DecodeError::UnsupportedMediaType(err)
+    }
+}
+
+/// An error returned when an input is malformed, or doesn't match the shape
+/// of the type it's decoded into.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::SyntaxError;
+///
+/// let err = SyntaxError::new("expected `,` or `}`").with_position(3, 14);
+/// assert_eq!(err.line(), Some(3));
+/// assert_eq!(err.column(), Some(14));
+/// assert_eq!(err.to_string(), "invalid input at line 3, column 14");
+/// ```
+#[derive(Debug)]
+pub struct SyntaxError {
+    position: Option<(usize, usize)>,
+    source: BoxError,
+}
+
+impl SyntaxError {
+    /// Create a new instance.
+    pub fn new(source: impl Into<BoxError>) -> Self {
+        Self {
+            position: None,
+            source: source.into(),
+        }
+    }
+
+    /// Records the line and column the error occurred at, both starting at 1.
+    pub fn with_position(mut self, line: usize, column: usize) -> Self {
+        self.position = Some((line, column));
+        self
+    }
+
+    /// The line the error occurred at, if known.
+    pub fn line(&self) -> Option<usize> {
+        self.position.map(|(line, _)| line)
+    }
+
+    /// The column the error occurred at, if known.
+    pub fn column(&self) -> Option<usize> {
+        self.position.map(|(_, column)| column)
+    }
+
+    /// Consumes the error, returning the error reported by the decoder.
+    pub fn into_source(self) -> BoxError {
+        self.source
+    }
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some((line, column)) => {
+                write!(f, "invalid input at line {}, column {}", line, column)
+            }
+            None => f.write_str("invalid input"),
+        }
+    }
+}
+
+impl Error for SyntaxError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+/// An error returned when no decoder is registered for a content type.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::UnsupportedMediaType;
+///
+/// let err = UnsupportedMediaType::new("image/png");
+/// assert_eq!(err.content_type(), "image/png");
+/// assert_eq!(err.to_string(), "unsupported media type `image/png`");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedMediaType {
+    content_type: String,
+}
+
+impl UnsupportedMediaType {
+    /// Create a new instance.
+    pub fn new(content_type: impl Into<String>) -> Self {
+        Self {
+            content_type: content_type.into(),
+        }
+    }
+
+    /// The content type which wasn't supported.
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+}
+
+impl fmt::Display for UnsupportedMediaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unsupported media type `{}`", self.content_type)
+    }
+}
+
+impl Error for UnsupportedMediaType {}
+
+/// An error returned when a conversion didn't complete in time.
+///
+/// This is returned by [`Conversion::timeout`](crate::Conversion::timeout)
+/// and [`Conversion::deadline`](crate::Conversion::deadline).
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::TimeoutError;
+///
+/// let err: TimeoutError<&str> = TimeoutError::Elapsed;
+/// assert_eq!(err.to_string(), "conversion timed out");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutError<E> {
+    /// The conversion didn't complete before the deadline.
+    Elapsed,
+    /// The conversion failed.
+    Conversion(E),
+}
+
+impl<E> TimeoutError<E> {
+    /// Returns `true` if the conversion timed out.
+    pub fn is_elapsed(&self) -> bool {
+        matches!(self, TimeoutError::Elapsed)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for TimeoutError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeoutError::Elapsed => write!(f, "conversion timed out"),
+            TimeoutError::Conversion(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for TimeoutError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TimeoutError::Elapsed => None,
+            TimeoutError::Conversion(err) => Some(err),
+        }
+    }
+}
+
+/// An error describing a failed conversion.
+///
+/// `ConversionError` records the names of the types being converted between,
+/// an optional path to the field which failed, an optional message, and the
+/// underlying error. Errors can be layered to describe where they came from as
+/// they bubble through several conversions, most conveniently through
+/// [`Conversion::context`](crate::Conversion::context).
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::ConversionError;
+///
+/// let err = ConversionError::between::<str, u32>()
+///     .with_field("zip")
+///     .with_field("address")
+///     .with_message("zip codes must have 5 digits");
+/// assert_eq!(err.field_path(), Some("address.zip"));
+/// assert_eq!(
+///     err.to_string(),
+///     "failed to convert `str` into `u32` at `address.zip`: zip codes must have 5 digits",
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct ConversionError {
+    source_type: Option<&'static str>,
+    target_type: Option<&'static str>,
+    field_path: Option<String>,
+    message: Option<Cow<'static, str>>,
+    source: Option<BoxError>,
+}
+
+impl ConversionError {
+    /// Create a new instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new instance describing a conversion from `T` into `U`.
+    pub fn between<T: ?Sized, U: ?Sized>() -> Self {
+        Self::new().with_source_type::<T>().with_target_type::<U>()
+    }
+
+    /// Records the type being converted from.
+    pub fn with_source_type<T: ?Sized>(self) -> Self {
+        self.with_source_type_name(any::type_name::<T>())
+    }
+
+    pub(crate) fn with_source_type_name(mut self, name: &'static str) -> Self {
+        self.source_type = Some(name);
+        self
+    }
+
+    /// Records the type being converted into.
+    pub fn with_target_type<U: ?Sized>(mut self) -> Self {
+        self.target_type = Some(any::type_name::<U>());
+        self
+    }
+
+    /// Prepends a field to the path of the field which failed.
+    ///
+    /// Call this as the error bubbles up through nested fields, innermost
+    /// field first.
+    pub fn with_field(mut self, field: &str) -> Self {
+        self.field_path = Some(match self.field_path.take() {
+            Some(path) => format!("{}.{}", field, path),
+            None => field.to_owned(),
+        });
+        self
+    }
+
+    /// Sets a message describing the failure.
+    pub fn with_message(mut self, message: impl Into<Cow<'static, str>>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Sets the underlying error.
+    pub fn with_source(mut self, source: impl Into<BoxError>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// The name of the type being converted from, if known.
+    pub fn source_type(&self) -> Option<&'static str> {
+        self.source_type
+    }
+
+    /// The name of the type being converted into, if known.
+    pub fn target_type(&self) -> Option<&'static str> {
+        self.target_type
+    }
+
+    /// The path of the field which failed, separated by dots.
+    pub fn field_path(&self) -> Option<&str> {
+        self.field_path.as_deref()
+    }
+
+    /// The message describing the failure.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.source_type, self.target_type) {
+            (Some(source), Some(target)) => {
+                write!(f, "failed to convert `{}` into `{}`", source, target)?
+            }
+            (Some(source), None) => write!(f, "failed to convert `{}`", source)?,
+            (None, Some(target)) => write!(f, "failed to convert into `{}`", target)?,
+            (None, None) => write!(f, "conversion failed")?,
+        }
+        if let Some(path) = &self.field_path {
+            write!(f, " at `{}`", path)?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ConversionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|err| err as &(dyn Error + 'static))
+    }
+}
+
+impl From<FieldError> for ConversionError {
+    fn from(err: FieldError) -> Self {
+        ConversionError::new()
+            .with_field(err.field)
+            .with_source(err.source)
+    }
+}
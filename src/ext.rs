@@ -0,0 +1,286 @@
+use core::any;
+use core::fmt;
+use core::future::{self, Future};
+use core::pin::{pin, Pin};
+use core::task::{Context, Poll};
+use std::borrow::Cow;
+use std::time::{Duration, Instant};
+
+use pin_project_lite::pin_project;
+
+use crate::error::BoxError;
+use crate::{timer, ConversionError, TimeoutError, TryFrom};
+
+/// Extension methods for [`TryInto`](crate::TryInto).
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::prelude::*;
+/// use async_convert::TryFrom;
+/// # futures_lite::future::block_on(async {
+///
+/// struct Even(u32);
+///
+/// impl TryFrom<u32> for Even {
+///     type Error = String;
+///
+///     async fn try_from(value: u32) -> Result<Self, Self::Error> {
+///         match value % 2 {
+///             0 => Ok(Even(value)),
+///             _ => Err(format!("{} is odd", value)),
+///         }
+///     }
+/// }
+///
+/// let res = 3_u32
+///     .try_convert::<Even>()
+///     .map(|even| even.0 / 2)
+///     .map_err(|err| format!("invalid input: {}", err))
+///     .await;
+/// assert_eq!(res, Err("invalid input: 3 is odd".into()));
+/// # });
+/// ```
+pub trait TryIntoExt: Sized {
+    /// Converts `self` into `U`, returning a future which can be combined with
+    /// other conversions.
+    fn try_convert<U>(self) -> Conversion<impl Future<Output = Result<U, U::Error>> + Send>
+    where
+        U: TryFrom<Self>;
+
+    /// Converts `self` into `U`, failing if the conversion takes longer than
+    /// `duration`.
+    fn try_into_within<U>(
+        self,
+        duration: Duration,
+    ) -> Conversion<impl Future<Output = Result<U, TimeoutError<U::Error>>> + Send>
+    where
+        U: TryFrom<Self>;
+
+    /// Converts `self` into `U`, failing if the conversion hasn't completed by
+    /// `deadline`.
+    fn try_into_until<U>(
+        self,
+        deadline: Instant,
+    ) -> Conversion<impl Future<Output = Result<U, TimeoutError<U::Error>>> + Send>
+    where
+        U: TryFrom<Self>;
+}
+
+impl<T> TryIntoExt for T {
+    fn try_convert<U>(self) -> Conversion<impl Future<Output = Result<U, U::Error>> + Send>
+    where
+        U: TryFrom<Self>,
+    {
+        Conversion {
+            future: U::try_from(self),
+            source_type: Some(any::type_name::<T>()),
+        }
+    }
+
+    fn try_into_within<U>(
+        self,
+        duration: Duration,
+    ) -> Conversion<impl Future<Output = Result<U, TimeoutError<U::Error>>> + Send>
+    where
+        U: TryFrom<Self>,
+    {
+        self.try_convert().timeout(duration)
+    }
+
+    fn try_into_until<U>(
+        self,
+        deadline: Instant,
+    ) -> Conversion<impl Future<Output = Result<U, TimeoutError<U::Error>>> + Send>
+    where
+        U: TryFrom<Self>,
+    {
+        self.try_convert().deadline(deadline)
+    }
+}
+
+pin_project! {
+    /// A future performing a conversion.
+    ///
+    /// This future is created by [`TryIntoExt::try_convert`], and provides
+    /// methods to combine the conversion with other operations.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct Conversion<F> {
+        #[pin]
+        future: F,
+        source_type: Option<&'static str>,
+    }
+}
+
+impl<F> fmt::Debug for Conversion<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Conversion").finish_non_exhaustive()
+    }
+}
+
+impl<F, T, E> Conversion<F>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    /// Wraps a future resolving to the output of a conversion.
+    pub fn new(future: F) -> Self {
+        Self {
+            future,
+            source_type: None,
+        }
+    }
+
+    /// Wraps the future created by `f`, keeping track of the source type.
+    fn chain<G, Fut>(self, f: G) -> Conversion<Fut>
+    where
+        G: FnOnce(Self) -> Fut,
+    {
+        let source_type = self.source_type;
+        Conversion {
+            future: f(self),
+            source_type,
+        }
+    }
+
+    /// Maps the converted value using a closure.
+    pub fn map<U, G>(self, f: G) -> Conversion<impl Future<Output = Result<U, E>>>
+    where
+        G: FnOnce(T) -> U,
+    {
+        self.chain(|this| async move { this.await.map(f) })
+    }
+
+    /// Maps the conversion error using a closure.
+    pub fn map_err<E2, G>(self, f: G) -> Conversion<impl Future<Output = Result<T, E2>>>
+    where
+        G: FnOnce(E) -> E2,
+    {
+        self.chain(|this| async move { this.await.map_err(f) })
+    }
+
+    /// Converts the converted value into `V`, chaining two conversions.
+    ///
+    /// The error of the second conversion is converted into the error of the
+    /// first using [`From`].
+    pub fn and_then<V>(self) -> Conversion<impl Future<Output = Result<V, E>>>
+    where
+        V: TryFrom<T>,
+        E: From<V::Error>,
+    {
+        self.chain(|this| async move { Ok(V::try_from(this.await?).await?) })
+    }
+
+    /// Fails the conversion if it takes longer than `duration`.
+    ///
+    /// The timer is selected through cargo features: `tokio` uses
+    /// `tokio::time`, `async-std` uses `async_std::task::sleep`, and without
+    /// either feature a built-in timer thread is used.
+    pub fn timeout(
+        self,
+        duration: Duration,
+    ) -> Conversion<impl Future<Output = Result<T, TimeoutError<E>>>> {
+        self.deadline(timer::deadline_after(duration))
+    }
+
+    /// Fails the conversion if it hasn't completed by `deadline`.
+    ///
+    /// See [`Conversion::timeout`] for how the timer is selected.
+    pub fn deadline(
+        self,
+        deadline: Instant,
+    ) -> Conversion<impl Future<Output = Result<T, TimeoutError<E>>>> {
+        self.chain(|this| async move {
+            let mut conversion = pin!(this);
+            let mut sleep = pin!(timer::sleep_until(deadline));
+            future::poll_fn(|cx| {
+                if let Poll::Ready(res) = conversion.as_mut().poll(cx) {
+                    return Poll::Ready(res.map_err(TimeoutError::Conversion));
+                }
+                match sleep.as_mut().poll(cx) {
+                    Poll::Ready(()) => Poll::Ready(Err(TimeoutError::Elapsed)),
+                    Poll::Pending => Poll::Pending,
+                }
+            })
+            .await
+        })
+    }
+
+    /// Recovers from a failed conversion using an async closure.
+    pub fn or_else<E2, G, Fut>(self, f: G) -> Conversion<impl Future<Output = Result<T, E2>>>
+    where
+        G: FnOnce(E) -> Fut,
+        Fut: Future<Output = Result<T, E2>>,
+    {
+        self.chain(|this| async move {
+            match this.await {
+                Ok(value) => Ok(value),
+                Err(err) => f(err).await,
+            }
+        })
+    }
+
+    /// Wraps the conversion error in a [`ConversionError`] with a message.
+    ///
+    /// The error records the name of the type being converted into, and the
+    /// name of the type being converted from if the conversion was created by
+    /// [`TryIntoExt::try_convert`]. Calling `context` on a conversion which
+    /// already fails with a `ConversionError` adds another layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_convert::{TryFrom, TryIntoExt};
+    /// # futures_lite::future::block_on(async {
+    ///
+    /// #[derive(Debug)]
+    /// struct Port(u16);
+    ///
+    /// impl TryFrom<&'static str> for Port {
+    ///     type Error = std::num::ParseIntError;
+    ///
+    ///     async fn try_from(s: &'static str) -> Result<Self, Self::Error> {
+    ///         s.parse().map(Port)
+    ///     }
+    /// }
+    ///
+    /// let err = "http"
+    ///     .try_convert::<Port>()
+    ///     .context("invalid listen address")
+    ///     .await
+    ///     .unwrap_err();
+    /// assert_eq!(err.message(), Some("invalid listen address"));
+    /// assert!(err.target_type().unwrap().ends_with("Port"));
+    /// assert_eq!(err.source_type(), Some("&str"));
+    /// # });
+    /// ```
+    pub fn context<M>(
+        self,
+        message: M,
+    ) -> Conversion<impl Future<Output = Result<T, ConversionError>>>
+    where
+        E: Into<BoxError>,
+        M: Into<Cow<'static, str>>,
+    {
+        let source_type = self.source_type;
+        self.chain(|this| async move {
+            this.await.map_err(|err| {
+                let err = ConversionError::new()
+                    .with_target_type::<T>()
+                    .with_message(message)
+                    .with_source(err);
+                match source_type {
+                    Some(source_type) => err.with_source_type_name(source_type),
+                    None => err,
+                }
+            })
+        })
+    }
+}
+
+impl<F: Future> Future for Conversion<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().future.poll(cx)
+    }
+}
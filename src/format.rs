@@ -0,0 +1,68 @@
+// Implements `TryFrom<Reader<R>>`, `TryFrom<Limited<R, N>>`, and
+// `TryFrom<Vec<u8>>` for a format wrapper. `$decode` decodes a value from a
+// byte slice, failing with a `DecodeError`.
+//
+// A single generic impl over the source type would overlap with the blanket
+// impl for `From`, so every source gets its own impl.
+macro_rules! impl_decode {
+    ($wrapper:ident, $decode:path) => {
+        impl<R, T> $crate::TryFrom<$crate::Reader<R>> for $wrapper<T>
+        where
+            R: ::futures_io::AsyncRead + Unpin + Send,
+            T: ::serde::de::DeserializeOwned,
+        {
+            type Error = $crate::DecodeError;
+
+            async fn try_from(reader: $crate::Reader<R>) -> Result<Self, Self::Error> {
+                let buf = <Vec<u8> as $crate::TryFrom<_>>::try_from(reader).await?;
+                $decode(&buf).map($wrapper)
+            }
+        }
+
+        impl<R, T, const N: usize> $crate::TryFrom<$crate::Limited<R, N>> for $wrapper<T>
+        where
+            R: ::futures_io::AsyncRead + Unpin + Send,
+            T: ::serde::de::DeserializeOwned,
+        {
+            type Error = $crate::DecodeError;
+
+            async fn try_from(reader: $crate::Limited<R, N>) -> Result<Self, Self::Error> {
+                let buf = <Vec<u8> as $crate::TryFrom<_>>::try_from(reader).await?;
+                $decode(&buf).map($wrapper)
+            }
+        }
+
+        impl<T> $crate::TryFrom<Vec<u8>> for $wrapper<T>
+        where
+            T: ::serde::de::DeserializeOwned,
+        {
+            type Error = $crate::DecodeError;
+
+            async fn try_from(buf: Vec<u8>) -> Result<Self, Self::Error> {
+                $decode(&buf).map($wrapper)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "ciborium")]
+mod cbor;
+#[cfg(feature = "serde_urlencoded")]
+mod form;
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(feature = "rmp-serde")]
+mod msgpack;
+#[cfg(feature = "toml")]
+mod toml;
+
+#[cfg(feature = "toml")]
+pub use self::toml::Toml;
+#[cfg(feature = "ciborium")]
+pub use cbor::Cbor;
+#[cfg(feature = "serde_urlencoded")]
+pub use form::Form;
+#[cfg(feature = "serde_json")]
+pub use json::Json;
+#[cfg(feature = "rmp-serde")]
+pub use msgpack::MsgPack;
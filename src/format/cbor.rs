@@ -0,0 +1,43 @@
+use serde::de::DeserializeOwned;
+
+use crate::{DecodeError, SyntaxError};
+
+/// Converts values from CBOR.
+///
+/// `Cbor<T>` implements `TryFrom<Reader<R>>`, `TryFrom<Limited<R, N>>`, and
+/// `TryFrom<Vec<u8>>` whenever `T` implements [`DeserializeOwned`]. Decoding
+/// fails with a [`DecodeError`], like the other format wrappers.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{Cbor, Reader, TryFrom};
+/// use serde::Deserialize;
+/// # futures_lite::future::block_on(async {
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// // `{ "name": "chashu" }`
+/// let body = Reader::new(&b"\xa1\x64name\x66chashu"[..]).limit(1024);
+/// let Cbor(user) = Cbor::<User>::try_from(body).await?;
+/// assert_eq!(user.name, "chashu");
+/// # Ok::<(), async_convert::DecodeError>(()) });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Cbor<T>(pub T);
+
+impl<T> Cbor<T> {
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+fn decode<T: DeserializeOwned>(buf: &[u8]) -> Result<T, DecodeError> {
+    ciborium::from_reader(buf).map_err(|err| SyntaxError::new(err).into())
+}
+
+impl_decode!(Cbor, decode);
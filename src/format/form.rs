@@ -0,0 +1,45 @@
+use serde::de::DeserializeOwned;
+
+use crate::{DecodeError, SyntaxError};
+
+/// Converts values from URL-encoded forms.
+///
+/// `Form<T>` implements `TryFrom<Reader<R>>`, `TryFrom<Limited<R, N>>`, and
+/// `TryFrom<Vec<u8>>` whenever `T` implements [`DeserializeOwned`], decoding
+/// `application/x-www-form-urlencoded` bodies. Decoding fails with a
+/// [`DecodeError`], like the other format wrappers.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{Form, Reader, TryFrom};
+/// use serde::Deserialize;
+/// # futures_lite::future::block_on(async {
+///
+/// #[derive(Deserialize)]
+/// struct Login {
+///     user: String,
+///     remember: bool,
+/// }
+///
+/// let body = Reader::new(&b"user=chashu&remember=true"[..]).limit(1024);
+/// let Form(login) = Form::<Login>::try_from(body).await?;
+/// assert_eq!(login.user, "chashu");
+/// assert!(login.remember);
+/// # Ok::<(), async_convert::DecodeError>(()) });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Form<T>(pub T);
+
+impl<T> Form<T> {
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+fn decode<T: DeserializeOwned>(buf: &[u8]) -> Result<T, DecodeError> {
+    serde_urlencoded::from_bytes(buf).map_err(|err| SyntaxError::new(err).into())
+}
+
+impl_decode!(Form, decode);
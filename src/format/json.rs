@@ -0,0 +1,74 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{DecodeError, SyntaxError, TryFrom};
+
+/// Converts values to and from JSON.
+///
+/// `Json<T>` implements `TryFrom<Reader<R>>`, `TryFrom<Limited<R, N>>`, and
+/// `TryFrom<Vec<u8>>` whenever `T` implements [`DeserializeOwned`], so a
+/// request body can be parsed with a single conversion. Readers are drained
+/// before decoding, and a limit set on the reader applies to the encoded
+/// input. Decoding fails with a [`DecodeError`], whose `Syntax` variant
+/// carries the line and column the input was rejected at.
+///
+/// In the other direction, `Vec<u8>` and `String` implement
+/// `TryFrom<Json<T>>` whenever `T` implements [`Serialize`].
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{Json, Reader, TryFrom};
+/// use serde::Deserialize;
+/// # futures_lite::future::block_on(async {
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// let body = Reader::new(&br#"{ "name": "chashu" }"#[..]).limit(1024);
+/// let Json(user) = Json::<User>::try_from(body).await?;
+/// assert_eq!(user.name, "chashu");
+/// # Ok::<(), async_convert::DecodeError>(()) });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Json<T>(pub T);
+
+impl<T> Json<T> {
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+fn decode<T: DeserializeOwned>(buf: &[u8]) -> Result<T, DecodeError> {
+    serde_json::from_slice(buf).map_err(|err| {
+        let (line, column) = (err.line(), err.column());
+        SyntaxError::new(err).with_position(line, column).into()
+    })
+}
+
+impl_decode!(Json, decode);
+
+impl<T> TryFrom<Json<T>> for Vec<u8>
+where
+    T: Serialize + Send,
+{
+    type Error = serde_json::Error;
+
+    async fn try_from(json: Json<T>) -> Result<Self, Self::Error> {
+        serde_json::to_vec(&json.0)
+    }
+}
+
+impl<T> TryFrom<Json<T>> for String
+where
+    T: Serialize + Send,
+{
+    type Error = serde_json::Error;
+
+    async fn try_from(json: Json<T>) -> Result<Self, Self::Error> {
+        serde_json::to_string(&json.0)
+    }
+}
@@ -0,0 +1,43 @@
+use serde::de::DeserializeOwned;
+
+use crate::{DecodeError, SyntaxError};
+
+/// Converts values from MessagePack.
+///
+/// `MsgPack<T>` implements `TryFrom<Reader<R>>`, `TryFrom<Limited<R, N>>`, and
+/// `TryFrom<Vec<u8>>` whenever `T` implements [`DeserializeOwned`]. Decoding
+/// fails with a [`DecodeError`], like the other format wrappers.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{MsgPack, Reader, TryFrom};
+/// use serde::Deserialize;
+/// # futures_lite::future::block_on(async {
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// // `{ "name": "chashu" }`
+/// let body = Reader::new(&b"\x81\xa4name\xa6chashu"[..]).limit(1024);
+/// let MsgPack(user) = MsgPack::<User>::try_from(body).await?;
+/// assert_eq!(user.name, "chashu");
+/// # Ok::<(), async_convert::DecodeError>(()) });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct MsgPack<T>(pub T);
+
+impl<T> MsgPack<T> {
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+fn decode<T: DeserializeOwned>(buf: &[u8]) -> Result<T, DecodeError> {
+    rmp_serde::from_slice(buf).map_err(|err| SyntaxError::new(err).into())
+}
+
+impl_decode!(MsgPack, decode);
@@ -0,0 +1,64 @@
+use std::str;
+
+use serde::de::DeserializeOwned;
+
+use crate::{DecodeError, SyntaxError};
+
+/// Converts values from TOML.
+///
+/// `Toml<T>` implements `TryFrom<Reader<R>>`, `TryFrom<Limited<R, N>>`, and
+/// `TryFrom<Vec<u8>>` whenever `T` implements [`DeserializeOwned`]. Decoding
+/// fails with a [`DecodeError`], whose `Syntax` variant carries the line and
+/// column the input was rejected at, if known.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{Reader, Toml, TryFrom};
+/// use serde::Deserialize;
+/// # futures_lite::future::block_on(async {
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     port: u16,
+/// }
+///
+/// let body = Reader::new(&b"port = 8080"[..]).limit(1024);
+/// let Toml(config) = Toml::<Config>::try_from(body).await?;
+/// assert_eq!(config.port, 8080);
+/// # Ok::<(), async_convert::DecodeError>(()) });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Toml<T>(pub T);
+
+impl<T> Toml<T> {
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+fn decode<T: DeserializeOwned>(buf: &[u8]) -> Result<T, DecodeError> {
+    let input = str::from_utf8(buf).map_err(SyntaxError::new)?;
+    toml::from_str(input).map_err(|err| {
+        let span = err.span();
+        let err = SyntaxError::new(err);
+        match span {
+            Some(span) => {
+                let (line, column) = position(input, span.start);
+                err.with_position(line, column).into()
+            }
+            None => err.into(),
+        }
+    })
+}
+
+/// Returns the line and column of a byte offset, both starting at 1.
+fn position(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+    (line, column)
+}
+
+impl_decode!(Toml, decode);
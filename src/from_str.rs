@@ -0,0 +1,133 @@
+use core::future::Future;
+
+use crate::TryFrom;
+
+/// Parse a value from a string.
+///
+/// This is the async counterpart of [`std::str::FromStr`], for parsers which
+/// need to perform async work such as validating the value against a remote
+/// catalog. It's usually called through [`ParseExt::parse_async`].
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::FromStr;
+///
+/// enum Region {
+///     UsEast,
+///     EuWest,
+/// }
+///
+/// impl FromStr for Region {
+///     type Err = String;
+///
+///     async fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         // pretend we're validating against a remote catalog here instead.
+///         match s {
+///             "us-east" => Ok(Region::UsEast),
+///             "eu-west" => Ok(Region::EuWest),
+///             _ => Err(format!("unknown region {:?}", s)),
+///         }
+///     }
+/// }
+/// ```
+pub trait FromStr: Sized {
+    /// The associated error which can be returned from parsing.
+    type Err;
+
+    /// Parses a string `s` to return a value of this type.
+    fn from_str(s: &str) -> impl Future<Output = Result<Self, Self::Err>> + Send;
+}
+
+/// Extension methods for parsing strings using [`FromStr`].
+///
+/// `str` already has an inherent `parse` method which always takes precedence,
+/// so this method is named `parse_async` instead.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{FromStr, ParseExt};
+/// # futures_lite::future::block_on(async {
+///
+/// struct Port(u16);
+///
+/// impl FromStr for Port {
+///     type Err = std::num::ParseIntError;
+///
+///     async fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         s.parse().map(Port)
+///     }
+/// }
+///
+/// let port: Port = "8080".parse_async().await?;
+/// assert_eq!(port.0, 8080);
+/// # Ok::<(), std::num::ParseIntError>(()) });
+/// ```
+pub trait ParseExt {
+    /// Parses this string into another type.
+    fn parse_async<F: FromStr>(&self) -> impl Future<Output = Result<F, F::Err>> + Send;
+}
+
+impl ParseExt for str {
+    fn parse_async<F: FromStr>(&self) -> impl Future<Output = Result<F, F::Err>> + Send {
+        F::from_str(self)
+    }
+}
+
+/// Bridges [`FromStr`] into [`TryFrom<String>`](TryFrom) and `TryFrom<&str>`.
+///
+/// `Parsed<T>` implements `TryFrom<String>` and `TryFrom<&str>` whenever `T`
+/// implements `FromStr`. Implementing `TryFrom<String>` for every `FromStr`
+/// type directly would overlap with the blanket impl for [`From`](crate::From)
+/// types, so the output is wrapped instead.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{FromStr, Parsed, TryFrom};
+/// # futures_lite::future::block_on(async {
+///
+/// struct Name(String);
+///
+/// impl FromStr for Name {
+///     type Err = &'static str;
+///
+///     async fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         match s.is_empty() {
+///             true => Err("names can't be empty"),
+///             false => Ok(Name(s.to_owned())),
+///         }
+///     }
+/// }
+///
+/// let Parsed(name) = Parsed::<Name>::try_from(String::from("chashu")).await?;
+/// assert_eq!(name.0, "chashu");
+/// # Ok::<(), &'static str>(()) });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Parsed<T>(pub T);
+
+impl<T> Parsed<T> {
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: FromStr> TryFrom<String> for Parsed<T> {
+    type Error = T::Err;
+
+    async fn try_from(value: String) -> Result<Self, Self::Error> {
+        T::from_str(&value).await.map(Parsed)
+    }
+}
+
+impl<'a, T: FromStr> TryFrom<&'a str> for Parsed<T> {
+    type Error = T::Err;
+
+    fn try_from(value: &'a str) -> impl Future<Output = Result<Self, Self::Error>> + Send {
+        let fut = T::from_str(value);
+        async move { fut.await.map(Parsed) }
+    }
+}
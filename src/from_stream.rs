@@ -0,0 +1,281 @@
+use core::future::{self, Future};
+use core::pin::pin;
+use std::collections::{BTreeSet, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+use futures_core::Stream;
+use futures_util::StreamExt;
+
+/// Conversion from a [`Stream`].
+///
+/// By implementing `FromStream` for a type, you define how it will be created
+/// from a stream. This is common for types which describe a collection of some
+/// kind. It is the async counterpart of
+/// [`FromIterator`](std::iter::FromIterator), and is usually called through
+/// [`collect`].
+///
+/// The future returned by `from_stream` is `Send`, so the stream and its items
+/// must be `Send` too.
+///
+/// This trait is only available with the `futures` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::FromStream;
+/// use futures_lite::stream;
+/// # futures_lite::future::block_on(async {
+///
+/// let numbers = Vec::from_stream(stream::iter(0..3)).await;
+/// assert_eq!(numbers, vec![0, 1, 2]);
+/// # });
+/// ```
+pub trait FromStream<T>: Sized {
+    /// Creates a value from a stream.
+    fn from_stream<S>(stream: S) -> impl Future<Output = Self> + Send
+    where
+        S: IntoStream<Item = T> + Send,
+        S::IntoStream: Send,
+        T: Send;
+}
+
+/// Conversion into a [`Stream`].
+///
+/// By implementing `IntoStream` for a type, you define how it will be converted
+/// into a stream. It is the async counterpart of [`IntoIterator`]. Every
+/// `Stream` implements `IntoStream` by returning itself.
+///
+/// This trait is only available with the `futures` feature enabled.
+pub trait IntoStream {
+    /// The type of the elements being streamed.
+    type Item;
+
+    /// Which kind of stream are we turning this into?
+    type IntoStream: Stream<Item = Self::Item>;
+
+    /// Creates a stream from a value.
+    fn into_stream(self) -> Self::IntoStream;
+}
+
+impl<S: Stream> IntoStream for S {
+    type Item = S::Item;
+    type IntoStream = S;
+
+    fn into_stream(self) -> Self::IntoStream {
+        self
+    }
+}
+
+/// Collects a stream into a collection.
+///
+/// This is the async counterpart of [`Iterator::collect`], using
+/// [`FromStream`] to create the collection.
+///
+/// This function is only available with the `futures` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeSet;
+/// use futures_lite::stream;
+/// # futures_lite::future::block_on(async {
+///
+/// let set: BTreeSet<_> = async_convert::collect(stream::iter(vec![3, 1, 3])).await;
+/// assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![1, 3]);
+/// # });
+/// ```
+pub async fn collect<C, S>(stream: S) -> C
+where
+    S: IntoStream + Send,
+    S::IntoStream: Send,
+    S::Item: Send,
+    C: FromStream<S::Item>,
+{
+    C::from_stream(stream).await
+}
+
+/// Collects a stream of results into a collection, stopping at the first
+/// error.
+///
+/// This function is only available with the `futures` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// use futures_lite::stream;
+/// # futures_lite::future::block_on(async {
+///
+/// let items = stream::iter(vec![Ok(1), Err("oops"), Ok(3)]);
+/// let res: Result<Vec<i32>, _> = async_convert::try_collect(items).await;
+/// assert_eq!(res, Err("oops"));
+/// # });
+/// ```
+pub async fn try_collect<C, T, E, S>(stream: S) -> Result<C, E>
+where
+    S: IntoStream<Item = Result<T, E>> + Send,
+    S::IntoStream: Send,
+    T: Send,
+    E: Send,
+    C: FromStream<T>,
+{
+    Result::from_stream(stream).await
+}
+
+impl<T> FromStream<T> for Vec<T> {
+    async fn from_stream<S>(stream: S) -> Self
+    where
+        S: IntoStream<Item = T> + Send,
+        S::IntoStream: Send,
+        T: Send,
+    {
+        let mut stream = pin!(stream.into_stream());
+        let mut out = Vec::with_capacity(stream.size_hint().0);
+        while let Some(item) = stream.next().await {
+            out.push(item);
+        }
+        out
+    }
+}
+
+impl FromStream<char> for String {
+    async fn from_stream<S>(stream: S) -> Self
+    where
+        S: IntoStream<Item = char> + Send,
+        S::IntoStream: Send,
+    {
+        let mut stream = pin!(stream.into_stream());
+        let mut out = String::new();
+        while let Some(c) = stream.next().await {
+            out.push(c);
+        }
+        out
+    }
+}
+
+impl<'a> FromStream<&'a str> for String {
+    async fn from_stream<S>(stream: S) -> Self
+    where
+        S: IntoStream<Item = &'a str> + Send,
+        S::IntoStream: Send,
+    {
+        let mut stream = pin!(stream.into_stream());
+        let mut out = String::new();
+        while let Some(s) = stream.next().await {
+            out.push_str(s);
+        }
+        out
+    }
+}
+
+impl FromStream<String> for String {
+    async fn from_stream<S>(stream: S) -> Self
+    where
+        S: IntoStream<Item = String> + Send,
+        S::IntoStream: Send,
+    {
+        let mut stream = pin!(stream.into_stream());
+        let mut out = String::new();
+        while let Some(s) = stream.next().await {
+            out.push_str(&s);
+        }
+        out
+    }
+}
+
+impl<K, V, H> FromStream<(K, V)> for HashMap<K, V, H>
+where
+    K: Eq + Hash + Send,
+    V: Send,
+    H: BuildHasher + Default + Send,
+{
+    async fn from_stream<S>(stream: S) -> Self
+    where
+        S: IntoStream<Item = (K, V)> + Send,
+        S::IntoStream: Send,
+    {
+        let mut stream = pin!(stream.into_stream());
+        let mut out = HashMap::with_hasher(H::default());
+        while let Some((key, value)) = stream.next().await {
+            out.insert(key, value);
+        }
+        out
+    }
+}
+
+impl<T: Ord> FromStream<T> for BTreeSet<T> {
+    async fn from_stream<S>(stream: S) -> Self
+    where
+        S: IntoStream<Item = T> + Send,
+        S::IntoStream: Send,
+        T: Send,
+    {
+        let mut stream = pin!(stream.into_stream());
+        let mut out = BTreeSet::new();
+        while let Some(item) = stream.next().await {
+            out.insert(item);
+        }
+        out
+    }
+}
+
+// Takes each element in the stream: if it is an `Err`, no further elements
+// are taken, and the `Err` is returned. Should no `Err` occur, a container
+// with the values of each `Result` is returned.
+impl<T, E, C> FromStream<Result<T, E>> for Result<C, E>
+where
+    T: Send,
+    E: Send,
+    C: FromStream<T>,
+{
+    async fn from_stream<S>(stream: S) -> Self
+    where
+        S: IntoStream<Item = Result<T, E>> + Send,
+        S::IntoStream: Send,
+    {
+        let mut error = None;
+        let items = stream.into_stream().scan(&mut error, |error, item| {
+            future::ready(match item {
+                Ok(value) => Some(value),
+                Err(err) => {
+                    **error = Some(err);
+                    None
+                }
+            })
+        });
+        let out = C::from_stream(items).await;
+        match error {
+            Some(err) => Err(err),
+            None => Ok(out),
+        }
+    }
+}
+
+// Takes each element in the stream: if it is `None`, no further elements are
+// taken, and `None` is returned. Should no `None` occur, a container with the
+// values of each `Option` is returned.
+impl<T, C> FromStream<Option<T>> for Option<C>
+where
+    T: Send,
+    C: FromStream<T>,
+{
+    async fn from_stream<S>(stream: S) -> Self
+    where
+        S: IntoStream<Item = Option<T>> + Send,
+        S::IntoStream: Send,
+    {
+        let mut found_none = false;
+        let items = stream
+            .into_stream()
+            .scan(&mut found_none, |found_none, item| {
+                if item.is_none() {
+                    **found_none = true;
+                }
+                future::ready(item)
+            });
+        let out = C::from_stream(items).await;
+        match found_none {
+            true => None,
+            false => Some(out),
+        }
+    }
+}
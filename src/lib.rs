@@ -12,19 +12,19 @@
 //! request and tries to create the body. This operation is fallible, and when
 //! writing async code also needs to be async.
 //!
-//! This crate provides traits for that, through the
-//! [`async_trait`](https://doc.rust-lang.org/std/convert/trait.TryFrom.html)
-//! crate. This is an experiment, but we'll likely want to extend `async-std`
-//! with this at some point too.
+//! This crate provides traits for that, using native `async fn` in traits.
+//! Conversions are statically dispatched and don't allocate: the future
+//! returned by `try_from` is a concrete type rather than a boxed trait object.
+//! This is an experiment, but we'll likely want to extend `async-std` with
+//! this at some point too.
 //!
 //! # Examples
 //!
 //! ```
-//! use async_convert::{async_trait, TryFrom};
+//! use async_convert::TryFrom;
 //!
 //! struct GreaterThanZero(i32);
 //!
-//! #[async_trait]
 //! impl TryFrom<i32> for GreaterThanZero {
 //!     type Error = &'static str;
 //!
@@ -39,16 +39,164 @@
 //! }
 //! ```
 
-#![forbid(unsafe_code, future_incompatible, rust_2018_idioms)]
-#![deny(missing_debug_implementations, nonstandard_style)]
+#![forbid(unsafe_code, future_incompatible)]
+// `rust_2018_idioms` is forbidden lint by lint, because `pin_project!`
+// expands to `#[allow(explicit_outlives_requirements)]`, which can't override
+// a `forbid`. That lint is denied instead.
+#![forbid(
+    bare_trait_objects,
+    elided_lifetimes_in_paths,
+    ellipsis_inclusive_range_patterns,
+    unused_extern_crates
+)]
+#![deny(
+    explicit_outlives_requirements,
+    missing_debug_implementations,
+    nonstandard_style
+)]
 #![warn(missing_docs, rustdoc::missing_doc_code_examples, unreachable_pub)]
 
-pub use async_trait::async_trait;
+use core::convert::Infallible;
+use core::future::Future;
+
+mod batch;
+mod blocking;
+mod error;
+mod ext;
+#[cfg(any(
+    feature = "ciborium",
+    feature = "rmp-serde",
+    feature = "serde_json",
+    feature = "serde_urlencoded",
+    feature = "toml"
+))]
+mod format;
+mod from_str;
+#[cfg(feature = "futures")]
+mod from_stream;
+#[cfg(feature = "futures-io")]
+mod negotiate;
+#[cfg(feature = "futures-io")]
+mod read;
+mod ready;
+mod retry;
+#[cfg(feature = "futures")]
+mod stream;
+mod timer;
+mod try_from_ref;
+mod try_from_with;
+
+pub use batch::{Batch, BatchMode, Collection, PartialConversion};
+pub use blocking::Blocking;
+pub use error::{
+    ConversionError, DecodeError, FieldError, LimitExceeded, SyntaxError, TimeoutError,
+    UnknownVariant, UnsupportedMediaType, VariantKey,
+};
+pub use ext::{Conversion, TryIntoExt};
+#[cfg(feature = "ciborium")]
+pub use format::Cbor;
+#[cfg(feature = "serde_urlencoded")]
+pub use format::Form;
+#[cfg(feature = "serde_json")]
+pub use format::Json;
+#[cfg(feature = "rmp-serde")]
+pub use format::MsgPack;
+#[cfg(feature = "toml")]
+pub use format::Toml;
+pub use from_str::{FromStr, ParseExt, Parsed};
+#[cfg(feature = "futures")]
+pub use from_stream::{collect, try_collect, FromStream, IntoStream};
+#[cfg(feature = "futures-io")]
+pub use negotiate::{Decoders, Negotiated};
+#[cfg(feature = "futures-io")]
+pub use read::{Limited, Reader};
+pub use ready::Ready;
+pub use retry::Retry;
+#[cfg(feature = "futures")]
+pub use stream::TryConvertStreamExt;
+pub use try_from_ref::{TryConvertRefExt, TryFromRef};
+pub use try_from_with::{TryFromWith, TryIntoWith};
+
+/// Derive macro generating an impl of the trait `TryFrom`.
+#[cfg(feature = "derive")]
+pub use async_convert_derive::TryFrom;
 
 /// A shared prelude.
 pub mod prelude {
+    pub use super::ParseExt as _;
+    pub use super::TryConvertRefExt as _;
+    #[cfg(feature = "futures")]
+    pub use super::TryConvertStreamExt as _;
     pub use super::TryFrom as _;
     pub use super::TryInto as _;
+    pub use super::TryIntoExt as _;
+}
+
+/// Used to do value-to-value conversions while consuming the input value. It is
+/// the reciprocal of [`Into`].
+///
+/// One should always prefer implementing `From` over [`Into`] because
+/// implementing `From` automatically provides one with an implementation of
+/// [`Into`] thanks to the blanket implementation in this crate.
+///
+/// `From` conversions must not fail. If the conversion can fail, implement
+/// [`TryFrom`] instead. Every `From` implementation also provides a
+/// [`TryFrom`] implementation with an [`Infallible`] error.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::From;
+///
+/// struct Config {
+///     retries: u8,
+/// }
+///
+/// impl From<()> for Config {
+///     async fn from(_: ()) -> Self {
+///         // pretend we're loading a default config from disk here instead.
+///         Config { retries: 3 }
+///     }
+/// }
+/// ```
+pub trait From<T>: Sized {
+    /// Performs the conversion.
+    fn from(value: T) -> impl Future<Output = Self> + Send;
+}
+
+/// A value-to-value conversion that consumes the input value. The
+/// opposite of [`From`].
+///
+/// One should avoid implementing `Into` and implement [`From`] instead.
+/// Implementing [`From`] automatically provides one with an implementation
+/// of `Into` thanks to the blanket implementation in this crate.
+pub trait Into<T>: Sized {
+    /// Performs the conversion.
+    fn into(self) -> impl Future<Output = T> + Send;
+}
+
+// From implies Into
+impl<T, U> Into<U> for T
+where
+    U: From<T>,
+{
+    fn into(self) -> impl Future<Output = U> + Send {
+        U::from(self)
+    }
+}
+
+// Infallible conversions are semantically equivalent to fallible conversions
+// with an uninhabited error type.
+impl<T, U> TryFrom<T> for U
+where
+    U: From<T>,
+{
+    type Error = Infallible;
+
+    fn try_from(value: T) -> impl Future<Output = Result<Self, Self::Error>> + Send {
+        let fut = U::from(value);
+        async move { Ok(fut.await) }
+    }
 }
 
 /// Simple and safe type conversions that may fail in a controlled
@@ -65,13 +213,15 @@ pub mod prelude {
 /// for perfect conversions, so the `TryFrom` trait informs the
 /// programmer when a type conversion could go bad and lets them
 /// decide how to handle it.
-#[async_trait]
+///
+/// Implementations may be written using `async fn`, as long as the returned
+/// future is `Send`.
 pub trait TryFrom<T>: Sized {
     /// The type returned in the event of a conversion error.
     type Error;
 
     /// Performs the conversion.
-    async fn try_from(value: T) -> Result<Self, Self::Error>;
+    fn try_from(value: T) -> impl Future<Output = Result<Self, Self::Error>> + Send;
 }
 
 /// An attempted conversion that consumes `self`, which may or may not be
@@ -84,28 +234,86 @@ pub trait TryFrom<T>: Sized {
 /// standard library. For more information on this, see the
 /// documentation for [`Into`].
 ///
+/// The future returned by `try_into` is `Send`, so conversions can be spawned
+/// on multi-threaded executors. For conversions which hold `!Send` state across
+/// `.await` points, see [`LocalTryInto`].
+///
 /// # Implementing `TryInto`
 ///
 /// This suffers the same restrictions and reasoning as implementing
 /// [`Into`], see there for details.
-#[async_trait(?Send)]
 pub trait TryInto<T>: Sized {
     /// The type returned in the event of a conversion error.
     type Error;
 
     /// Performs the conversion.
-    async fn try_into(self) -> Result<T, Self::Error>;
+    fn try_into(self) -> impl Future<Output = Result<T, Self::Error>> + Send;
 }
 
 // TryFrom implies TryInto
-#[async_trait(?Send)]
 impl<T, U> TryInto<U> for T
 where
     U: TryFrom<T>,
 {
     type Error = U::Error;
 
-    async fn try_into(self) -> Result<U, U::Error> {
-        U::try_from(self).await
+    fn try_into(self) -> impl Future<Output = Result<U, U::Error>> + Send {
+        U::try_from(self)
+    }
+}
+
+/// A variant of [`TryFrom`] whose future is not required to be `Send`.
+///
+/// This is useful for conversions which hold `Rc`, `RefCell`, or other `!Send`
+/// state across `.await` points, and are only ever run on single-threaded
+/// executors. It is the reciprocal of [`LocalTryInto`].
+///
+/// Every [`TryFrom`] implementation is also a `LocalTryFrom` implementation,
+/// so bounds on `LocalTryFrom` accept both kinds of conversions.
+///
+/// Both `TryFrom` and `LocalTryFrom` provide a method named `try_from`, so
+/// importing both traits into the same scope will make calls ambiguous.
+pub trait LocalTryFrom<T>: Sized {
+    /// The type returned in the event of a conversion error.
+    type Error;
+
+    /// Performs the conversion.
+    fn try_from(value: T) -> impl Future<Output = Result<Self, Self::Error>>;
+}
+
+/// A variant of [`TryInto`] whose future is not required to be `Send`.
+///
+/// Library authors should usually not directly implement this trait,
+/// but should prefer implementing the [`LocalTryFrom`] trait, which provides
+/// an equivalent `LocalTryInto` implementation for free.
+pub trait LocalTryInto<T>: Sized {
+    /// The type returned in the event of a conversion error.
+    type Error;
+
+    /// Performs the conversion.
+    fn try_into(self) -> impl Future<Output = Result<T, Self::Error>>;
+}
+
+// TryFrom implies LocalTryFrom
+impl<T, U> LocalTryFrom<T> for U
+where
+    U: TryFrom<T>,
+{
+    type Error = U::Error;
+
+    fn try_from(value: T) -> impl Future<Output = Result<U, U::Error>> {
+        <U as TryFrom<T>>::try_from(value)
+    }
+}
+
+// LocalTryFrom implies LocalTryInto
+impl<T, U> LocalTryInto<U> for T
+where
+    U: LocalTryFrom<T>,
+{
+    type Error = U::Error;
+
+    fn try_into(self) -> impl Future<Output = Result<U, U::Error>> {
+        U::try_from(self)
     }
 }
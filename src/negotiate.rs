@@ -0,0 +1,248 @@
+use core::future::Future;
+use core::pin::Pin;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str;
+
+use futures_io::AsyncRead;
+
+use crate::{
+    DecodeError, Limited, Reader, SyntaxError, TryFrom, TryFromWith, UnsupportedMediaType,
+};
+
+/// A boxed future resolving to a decoded value.
+type DecodeFuture<T> = Pin<Box<dyn Future<Output = Result<T, DecodeError>> + Send>>;
+
+/// A decoder registered for a content type.
+type Decoder<T> = Box<dyn Fn(Vec<u8>) -> DecodeFuture<T> + Send + Sync>;
+
+/// Decodes a value using a decoder picked by its content type at runtime.
+///
+/// `Negotiated<T>` implements `TryFromWith<(C, Reader<R>), Decoders<T>>` and
+/// `TryFromWith<(C, Limited<R, N>), Decoders<T>>`, where `C` is the content
+/// type of the input, such as the value of a `Content-Type` header. The
+/// decoder registered for the content type is looked up before the reader is
+/// drained, and the conversion fails with
+/// [`DecodeError::UnsupportedMediaType`] if there is none.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{Decoders, Negotiated, Reader, TryIntoWith};
+/// # futures_lite::future::block_on(async {
+///
+/// let decoders = Decoders::<String>::new().text();
+///
+/// let body = Reader::new(&b"hello"[..]).limit(1024);
+/// let Negotiated(text) = ("text/plain; charset=utf-8", body)
+///     .try_into_with(&decoders)
+///     .await?;
+/// assert_eq!(text, "hello");
+///
+/// let body = Reader::new(&b"hello"[..]);
+/// let res: Result<Negotiated<String>, _> = ("image/png", body).try_into_with(&decoders).await;
+/// assert!(res.is_err());
+/// # Ok::<(), async_convert::DecodeError>(()) });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Negotiated<T>(pub T);
+
+impl<T> Negotiated<T> {
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// The decoders [`Negotiated`] picks from, keyed by content type.
+///
+/// Content types are matched on their type and subtype, ignoring case and
+/// parameters such as `charset`. Decoders for the built-in formats are
+/// registered using the method named after the format, and other decoders
+/// are registered using [`Decoders::register`].
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{Decoders, Negotiated, Reader, SyntaxError, TryIntoWith};
+/// # futures_lite::future::block_on(async {
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Csv(Vec<String>);
+///
+/// let decoders = Decoders::new().register("text/csv", |buf: Vec<u8>| async move {
+///     let text = String::from_utf8(buf).map_err(SyntaxError::new)?;
+///     Ok(Csv(text.split(',').map(String::from).collect()))
+/// });
+///
+/// let body = Reader::new(&b"a,b"[..]);
+/// let Negotiated(csv) = ("text/csv", body).try_into_with(&decoders).await?;
+/// assert_eq!(csv, Csv(vec!["a".into(), "b".into()]));
+/// # Ok::<(), async_convert::DecodeError>(()) });
+/// ```
+pub struct Decoders<T> {
+    decoders: HashMap<String, Decoder<T>>,
+}
+
+impl<T: 'static> Decoders<T> {
+    /// Create a new instance without any decoders.
+    pub fn new() -> Self {
+        Self {
+            decoders: HashMap::new(),
+        }
+    }
+
+    /// Registers a decoder for `content_type`, replacing any decoder which
+    /// was registered for it before.
+    pub fn register<F, Fut>(mut self, content_type: &str, decoder: F) -> Self
+    where
+        F: Fn(Vec<u8>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, DecodeError>> + Send + 'static,
+    {
+        let decoder: Decoder<T> = Box::new(move |buf| Box::pin(decoder(buf)));
+        self.decoders.insert(essence(content_type), decoder);
+        self
+    }
+
+    /// Registers a decoder for `text/plain`, parsing the input using
+    /// [`std::str::FromStr`].
+    pub fn text(self) -> Self
+    where
+        T: str::FromStr,
+        T::Err: Error + Send + Sync + 'static,
+    {
+        self.register("text/plain", |buf| async move {
+            let text = str::from_utf8(&buf).map_err(SyntaxError::new)?;
+            text.parse().map_err(|err| SyntaxError::new(err).into())
+        })
+    }
+
+    /// Registers a decoder for `application/json`.
+    #[cfg(feature = "serde_json")]
+    pub fn json(self) -> Self
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.register("application/json", |buf| async move {
+            crate::Json::try_from(buf)
+                .await
+                .map(crate::Json::into_inner)
+        })
+    }
+
+    /// Registers a decoder for `application/x-www-form-urlencoded`.
+    #[cfg(feature = "serde_urlencoded")]
+    pub fn form(self) -> Self
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.register("application/x-www-form-urlencoded", |buf| async move {
+            crate::Form::try_from(buf)
+                .await
+                .map(crate::Form::into_inner)
+        })
+    }
+
+    /// Registers a decoder for `application/cbor`.
+    #[cfg(feature = "ciborium")]
+    pub fn cbor(self) -> Self
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.register("application/cbor", |buf| async move {
+            crate::Cbor::try_from(buf)
+                .await
+                .map(crate::Cbor::into_inner)
+        })
+    }
+
+    /// Registers a decoder for `application/msgpack`.
+    #[cfg(feature = "rmp-serde")]
+    pub fn msgpack(self) -> Self
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.register("application/msgpack", |buf| async move {
+            crate::MsgPack::try_from(buf)
+                .await
+                .map(crate::MsgPack::into_inner)
+        })
+    }
+
+    /// Registers a decoder for `application/toml`.
+    #[cfg(feature = "toml")]
+    pub fn toml(self) -> Self
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.register("application/toml", |buf| async move {
+            crate::Toml::try_from(buf)
+                .await
+                .map(crate::Toml::into_inner)
+        })
+    }
+}
+
+impl<T> Decoders<T> {
+    /// Returns the decoder registered for `content_type`.
+    fn get(&self, content_type: &str) -> Result<&Decoder<T>, UnsupportedMediaType> {
+        self.decoders
+            .get(&essence(content_type))
+            .ok_or_else(|| UnsupportedMediaType::new(content_type))
+    }
+}
+
+impl<T: 'static> Default for Decoders<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for Decoders<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Decoders")
+            .field("content_types", &self.decoders.keys())
+            .finish()
+    }
+}
+
+/// Strips the parameters from a content type, and normalizes its case.
+fn essence(content_type: &str) -> String {
+    let essence = content_type.split(';').next().unwrap_or_default();
+    essence.trim().to_ascii_lowercase()
+}
+
+impl<C, R, T> TryFromWith<(C, Reader<R>), Decoders<T>> for Negotiated<T>
+where
+    C: AsRef<str> + Send,
+    R: AsyncRead + Unpin + Send,
+{
+    type Error = DecodeError;
+
+    async fn try_from_with(
+        (content_type, reader): (C, Reader<R>),
+        decoders: &Decoders<T>,
+    ) -> Result<Self, Self::Error> {
+        let decoder = decoders.get(content_type.as_ref())?;
+        let buf = Vec::<u8>::try_from(reader).await?;
+        decoder(buf).await.map(Negotiated)
+    }
+}
+
+impl<C, R, T, const N: usize> TryFromWith<(C, Limited<R, N>), Decoders<T>> for Negotiated<T>
+where
+    C: AsRef<str> + Send,
+    R: AsyncRead + Unpin + Send,
+{
+    type Error = DecodeError;
+
+    async fn try_from_with(
+        (content_type, reader): (C, Limited<R, N>),
+        decoders: &Decoders<T>,
+    ) -> Result<Self, Self::Error> {
+        let decoder = decoders.get(content_type.as_ref())?;
+        let buf = Vec::<u8>::try_from(reader).await?;
+        decoder(buf).await.map(Negotiated)
+    }
+}
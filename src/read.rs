@@ -0,0 +1,224 @@
+use core::future::poll_fn;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::io::{self, ErrorKind};
+
+use futures_io::AsyncRead;
+
+use crate::{LimitExceeded, TryFrom};
+
+/// Drains an [`AsyncRead`] source into bytes or a string.
+///
+/// `Vec<u8>` and `String` implement `TryFrom<Reader<R>>` for any reader which
+/// is `AsyncRead + Unpin + Send`. With the `bytes` feature enabled,
+/// `bytes::Bytes` does too. Conversions into `String` fail with
+/// [`ErrorKind::InvalidData`] if the input isn't valid UTF-8.
+///
+/// By default the whole reader is drained. Use [`Reader::limit`] to fail the
+/// conversion once more than a given number of bytes have been read, or wrap
+/// the reader in [`Limited`] to set the limit in its type. Either way the
+/// conversion fails with an `io::Error` wrapping [`LimitExceeded`].
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{Reader, TryFrom};
+/// # futures_lite::future::block_on(async {
+///
+/// let body = String::try_from(Reader::new(&b"hello world"[..])).await?;
+/// assert_eq!(body, "hello world");
+///
+/// let res = Vec::<u8>::try_from(Reader::new(&b"hello world"[..]).limit(5)).await;
+/// assert!(res.is_err());
+/// # Ok::<(), std::io::Error>(()) });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Reader<R> {
+    reader: R,
+    limit: Option<usize>,
+}
+
+impl<R> Reader<R> {
+    /// Wraps a reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            limit: None,
+        }
+    }
+
+    /// Fails the conversion if the reader yields more than `limit` bytes.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Unwraps the reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R> Reader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Reads until EOF, enforcing the limit if one was set.
+    async fn read_to_end(mut self) -> io::Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        let mut chunk = [0; 8 * 1024];
+        loop {
+            let res = poll_fn(|cx| Pin::new(&mut self.reader).poll_read(cx, &mut chunk)).await;
+            let n = match res {
+                Ok(0) => return Ok(buf),
+                Ok(n) => n,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            if let Some(limit) = self.limit {
+                if buf.len() + n > limit {
+                    return Err(LimitExceeded::new(limit, buf.len() + n).into());
+                }
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        }
+    }
+}
+
+impl<R> TryFrom<Reader<R>> for Vec<u8>
+where
+    R: AsyncRead + Unpin + Send,
+{
+    type Error = io::Error;
+
+    async fn try_from(reader: Reader<R>) -> Result<Self, Self::Error> {
+        reader.read_to_end().await
+    }
+}
+
+impl<R> TryFrom<Reader<R>> for String
+where
+    R: AsyncRead + Unpin + Send,
+{
+    type Error = io::Error;
+
+    async fn try_from(reader: Reader<R>) -> Result<Self, Self::Error> {
+        let buf = reader.read_to_end().await?;
+        String::from_utf8(buf).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<R> TryFrom<Reader<R>> for bytes::Bytes
+where
+    R: AsyncRead + Unpin + Send,
+{
+    type Error = io::Error;
+
+    async fn try_from(reader: Reader<R>) -> Result<Self, Self::Error> {
+        reader.read_to_end().await.map(bytes::Bytes::from)
+    }
+}
+
+/// A reader which fails once more than `N` bytes have been read from it.
+///
+/// `Limited<R, N>` implements [`AsyncRead`] when `R: Unpin`, so it can be
+/// used to cap any reader. Reading past the limit fails with an `io::Error` of
+/// kind [`ErrorKind::InvalidData`] wrapping a [`LimitExceeded`]. Like
+/// [`Reader`], it can be converted into `Vec<u8>`, `String`, and
+/// `bytes::Bytes`.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{LimitExceeded, Limited, TryFrom};
+/// # futures_lite::future::block_on(async {
+///
+/// type Body<R> = Limited<R, 5>;
+///
+/// let body = String::try_from(Body::new(&b"hello"[..])).await?;
+/// assert_eq!(body, "hello");
+///
+/// let err = String::try_from(Body::new(&b"hello world"[..])).await.unwrap_err();
+/// let limit = err.get_ref().unwrap().downcast_ref::<LimitExceeded>().unwrap();
+/// assert_eq!(limit.read(), 11);
+/// # Ok::<(), std::io::Error>(()) });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Limited<R, const N: usize> {
+    reader: R,
+    read: usize,
+}
+
+impl<R, const N: usize> Limited<R, N> {
+    /// Wraps a reader.
+    pub fn new(reader: R) -> Self {
+        Self { reader, read: 0 }
+    }
+
+    /// The number of bytes read so far.
+    pub fn read(&self) -> usize {
+        self.read
+    }
+
+    /// Unwraps the reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R, const N: usize> AsyncRead for Limited<R, N>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let n = match Pin::new(&mut this.reader).poll_read(cx, buf) {
+            Poll::Ready(Ok(n)) => n,
+            other => return other,
+        };
+        this.read += n;
+        if this.read > N {
+            return Poll::Ready(Err(LimitExceeded::new(N, this.read).into()));
+        }
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl<R, const N: usize> TryFrom<Limited<R, N>> for Vec<u8>
+where
+    R: AsyncRead + Unpin + Send,
+{
+    type Error = io::Error;
+
+    async fn try_from(reader: Limited<R, N>) -> Result<Self, Self::Error> {
+        Reader::new(reader).read_to_end().await
+    }
+}
+
+impl<R, const N: usize> TryFrom<Limited<R, N>> for String
+where
+    R: AsyncRead + Unpin + Send,
+{
+    type Error = io::Error;
+
+    async fn try_from(reader: Limited<R, N>) -> Result<Self, Self::Error> {
+        String::try_from(Reader::new(reader)).await
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<R, const N: usize> TryFrom<Limited<R, N>> for bytes::Bytes
+where
+    R: AsyncRead + Unpin + Send,
+{
+    type Error = io::Error;
+
+    async fn try_from(reader: Limited<R, N>) -> Result<Self, Self::Error> {
+        bytes::Bytes::try_from(Reader::new(reader)).await
+    }
+}
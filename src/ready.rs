@@ -0,0 +1,48 @@
+use core::future::{self, Future};
+
+use crate::TryFrom;
+
+/// Lifts synchronous [`std::convert::TryFrom`] and [`std::convert::TryInto`]
+/// conversions into the async [`TryFrom`] and [`TryInto`](crate::TryInto)
+/// traits.
+///
+/// `Ready<U>` implements `TryFrom<Ready<T>>` whenever `T` implements
+/// `std::convert::TryInto<U>`. The conversion runs immediately and is returned
+/// as an already-completed future. Both sides of the conversion are wrapped so
+/// that the impl can't overlap with the blanket impls in this crate.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{Ready, TryFrom};
+/// # futures_lite::future::block_on(async {
+///
+/// let Ready(small) = Ready::<u8>::try_from(Ready(12_i64)).await?;
+/// assert_eq!(small, 12);
+///
+/// let res = Ready::<u8>::try_from(Ready(300_i64)).await;
+/// assert!(res.is_err());
+/// # Ok::<(), std::num::TryFromIntError>(()) });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Ready<T>(pub T);
+
+impl<T> Ready<T> {
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, U> TryFrom<Ready<T>> for Ready<U>
+where
+    T: core::convert::TryInto<U>,
+    U: Send,
+    T::Error: Send,
+{
+    type Error = T::Error;
+
+    fn try_from(value: Ready<T>) -> impl Future<Output = Result<Self, Self::Error>> + Send {
+        future::ready(value.0.try_into().map(Ready))
+    }
+}
@@ -0,0 +1,189 @@
+use core::fmt;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use crate::{timer, TryFrom};
+
+/// The predicate deciding whether an error is retried.
+type Predicate<E> = Box<dyn Fn(&E) -> bool + Send + Sync>;
+
+/// Retries conversions which fail transiently, with exponential backoff.
+///
+/// Because [`TryFrom::try_from`] consumes its input, the input is either
+/// cloned for every attempt using [`Retry::try_from`], or created by a closure
+/// using [`Retry::try_from_with`].
+///
+/// By default up to 3 attempts are made, waiting 100ms before the first
+/// retry and doubling the wait up to 10s. Every error is retried unless a
+/// predicate is set using [`Retry::retry_if`].
+///
+/// Waiting uses the timer selected through cargo features: `tokio`,
+/// `async-std`, or a built-in timer thread.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{Retry, TryFrom};
+/// use std::sync::atomic::{AtomicU32, Ordering};
+/// use std::time::Duration;
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     #[cfg(feature = "tokio")]
+/// #     let rt = tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap();
+/// #     #[cfg(feature = "tokio")]
+/// #     return rt.block_on(future);
+/// #     #[cfg(not(feature = "tokio"))]
+/// #     return futures_lite::future::block_on(future);
+/// # }
+/// # block_on(async {
+///
+/// static CALLS: AtomicU32 = AtomicU32::new(0);
+///
+/// #[derive(Debug, PartialEq)]
+/// enum LookupError {
+///     Unavailable,
+///     NotFound,
+/// }
+///
+/// struct User(u64);
+///
+/// impl TryFrom<u64> for User {
+///     type Error = LookupError;
+///
+///     async fn try_from(id: u64) -> Result<Self, Self::Error> {
+///         // pretend the first lookup hits a flaky backend.
+///         match CALLS.fetch_add(1, Ordering::SeqCst) {
+///             0 => Err(LookupError::Unavailable),
+///             _ => Ok(User(id)),
+///         }
+///     }
+/// }
+///
+/// let user = Retry::new()
+///     .max_attempts(5)
+///     .backoff(Duration::from_millis(1), Duration::from_millis(10))
+///     .retry_if(|err| *err == LookupError::Unavailable)
+///     .try_from::<u64, User>(12)
+///     .await?;
+/// assert_eq!(user.0, 12);
+/// assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+/// # Ok::<(), LookupError>(()) });
+/// ```
+pub struct Retry<E> {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    predicate: Option<Predicate<E>>,
+}
+
+impl<E> Retry<E> {
+    /// Create a new instance.
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+            predicate: None,
+        }
+    }
+
+    /// Sets the maximum number of attempts, including the first one.
+    ///
+    /// A value of `0` is treated as `1`.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the wait before the first retry, and the maximum wait between
+    /// retries. The wait doubles after every attempt.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Sets whether waits are randomized. Enabled by default.
+    ///
+    /// With jitter enabled every wait is randomly chosen between half and the
+    /// full backoff, so that many failing conversions don't retry in lockstep.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Only retries errors for which `predicate` returns `true`.
+    pub fn retry_if<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&E) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Some(Box::new(predicate));
+        self
+    }
+
+    /// Converts `value` into `U`, cloning it for every attempt.
+    pub async fn try_from<T, U>(&self, value: T) -> Result<U, E>
+    where
+        T: Clone,
+        U: TryFrom<T, Error = E>,
+    {
+        self.try_from_with(|| value.clone()).await
+    }
+
+    /// Converts values created by `input` into `U`, calling it once for every
+    /// attempt.
+    pub async fn try_from_with<T, U, F>(&self, mut input: F) -> Result<U, E>
+    where
+        F: FnMut() -> T,
+        U: TryFrom<T, Error = E>,
+    {
+        let mut backoff = self.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            let err = match U::try_from(input()).await {
+                Ok(value) => return Ok(value),
+                Err(err) => err,
+            };
+
+            let retryable = self.predicate.as_ref().map_or(true, |f| f(&err));
+            if attempt >= self.max_attempts || !retryable {
+                return Err(err);
+            }
+
+            timer::sleep_until(timer::deadline_after(self.delay(backoff))).await;
+            backoff = backoff.saturating_mul(2).min(self.max_backoff);
+            attempt += 1;
+        }
+    }
+
+    /// Computes how long to wait for the given backoff.
+    fn delay(&self, backoff: Duration) -> Duration {
+        if !self.jitter {
+            return backoff;
+        }
+        let half = backoff / 2;
+        let random = RandomState::new().build_hasher().finish();
+        let nanos = half.as_nanos().max(1);
+        half + Duration::from_nanos((u128::from(random) % nanos) as u64)
+    }
+}
+
+impl<E> Default for Retry<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> fmt::Debug for Retry<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Retry")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("jitter", &self.jitter)
+            .field("predicate", &self.predicate.is_some())
+            .finish()
+    }
+}
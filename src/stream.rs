@@ -0,0 +1,83 @@
+use futures_core::Stream;
+use futures_util::StreamExt;
+
+use crate::TryFrom;
+
+/// Extension methods converting every item of a [`Stream`].
+///
+/// This trait is only available with the `futures` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{TryConvertStreamExt, TryFrom};
+/// use futures_lite::stream::{self, StreamExt};
+/// # futures_lite::future::block_on(async {
+///
+/// struct Message(String);
+///
+/// impl TryFrom<Vec<u8>> for Message {
+///     type Error = std::string::FromUtf8Error;
+///
+///     async fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+///         String::from_utf8(bytes).map(Message)
+///     }
+/// }
+///
+/// let frames = stream::iter(vec![b"hello".to_vec(), vec![0xff]]);
+/// let messages: Vec<_> = frames.try_convert_buffered::<Message>(8).collect().await;
+/// assert_eq!(messages[0].as_ref().unwrap().0, "hello");
+/// assert!(messages[1].is_err());
+/// # });
+/// ```
+pub trait TryConvertStreamExt: Stream + Sized {
+    /// Converts every item into `U`, one at a time.
+    fn try_convert_each<U>(self) -> impl Stream<Item = Result<U, U::Error>>
+    where
+        U: TryFrom<Self::Item>;
+
+    /// Converts every item into `U`, running up to `limit` conversions at the
+    /// same time. Outputs are yielded in the order of the input.
+    ///
+    /// A `limit` of `0` is treated as `1`.
+    fn try_convert_buffered<U>(self, limit: usize) -> impl Stream<Item = Result<U, U::Error>>
+    where
+        U: TryFrom<Self::Item>;
+
+    /// Converts every item into `U`, running up to `limit` conversions at the
+    /// same time. Outputs are yielded as soon as they complete.
+    ///
+    /// A `limit` of `0` is treated as `1`.
+    fn try_convert_buffer_unordered<U>(
+        self,
+        limit: usize,
+    ) -> impl Stream<Item = Result<U, U::Error>>
+    where
+        U: TryFrom<Self::Item>;
+}
+
+impl<S: Stream> TryConvertStreamExt for S {
+    fn try_convert_each<U>(self) -> impl Stream<Item = Result<U, U::Error>>
+    where
+        U: TryFrom<Self::Item>,
+    {
+        self.then(U::try_from)
+    }
+
+    fn try_convert_buffered<U>(self, limit: usize) -> impl Stream<Item = Result<U, U::Error>>
+    where
+        U: TryFrom<Self::Item>,
+    {
+        self.map(U::try_from).buffered(limit.max(1))
+    }
+
+    fn try_convert_buffer_unordered<U>(
+        self,
+        limit: usize,
+    ) -> impl Stream<Item = Result<U, U::Error>>
+    where
+        U: TryFrom<Self::Item>,
+    {
+        self.map(U::try_from).buffer_unordered(limit.max(1))
+    }
+}
@@ -0,0 +1,44 @@
+use core::future::Future;
+use std::time::{Duration, Instant};
+
+#[cfg(not(any(feature = "tokio", feature = "async-std")))]
+mod thread;
+
+/// Roughly 30 years, the deadline used for durations which can't be added to
+/// the current time.
+const FAR_FUTURE: Duration = Duration::from_secs(86400 * 365 * 30);
+
+/// Returns the instant `duration` from now, clamped to a far-future deadline
+/// if it would overflow.
+pub(crate) fn deadline_after(duration: Duration) -> Instant {
+    let now = Instant::now();
+    now.checked_add(duration)
+        .unwrap_or_else(|| now + FAR_FUTURE)
+}
+
+/// Waits until `deadline` has been reached.
+///
+/// The timer is selected through the same cargo features as the blocking
+/// thread pool: `tokio`, `async-std`, or a built-in timer thread.
+#[cfg(feature = "tokio")]
+pub(crate) fn sleep_until(deadline: Instant) -> impl Future<Output = ()> + Send {
+    tokio::time::sleep_until(tokio::time::Instant::from_std(deadline))
+}
+
+/// Waits until `deadline` has been reached.
+///
+/// The timer is selected through the same cargo features as the blocking
+/// thread pool: `tokio`, `async-std`, or a built-in timer thread.
+#[cfg(all(feature = "async-std", not(feature = "tokio")))]
+pub(crate) fn sleep_until(deadline: Instant) -> impl Future<Output = ()> + Send {
+    async_std::task::sleep(deadline.saturating_duration_since(Instant::now()))
+}
+
+/// Waits until `deadline` has been reached.
+///
+/// The timer is selected through the same cargo features as the blocking
+/// thread pool: `tokio`, `async-std`, or a built-in timer thread.
+#[cfg(not(any(feature = "tokio", feature = "async-std")))]
+pub(crate) fn sleep_until(deadline: Instant) -> impl Future<Output = ()> + Send {
+    thread::Sleep::new(deadline)
+}
@@ -0,0 +1,197 @@
+//! A minimal timer driven by a background thread.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::Instant;
+
+/// The state of a pending `Sleep`, shared with the timer thread.
+type Slot = Arc<Mutex<Waiter>>;
+
+struct Waiter {
+    /// The waker to wake once the deadline has been reached.
+    waker: Option<Waker>,
+    /// Whether the timer thread has removed the entry from the heap.
+    fired: bool,
+}
+
+struct Timer {
+    state: Mutex<State>,
+    condvar: Condvar,
+}
+
+struct State {
+    entries: BinaryHeap<Entry>,
+    /// The number of entries whose `Sleep` was dropped before firing.
+    cancelled: usize,
+}
+
+impl Timer {
+    fn get() -> &'static Timer {
+        static TIMER: OnceLock<Timer> = OnceLock::new();
+        TIMER.get_or_init(|| {
+            // The thread blocks on `get` until initialization has completed.
+            thread::Builder::new()
+                .name("async-convert/timer".into())
+                .spawn(|| Timer::get().run())
+                .expect("failed to spawn the timer thread");
+            Timer {
+                state: Mutex::new(State {
+                    entries: BinaryHeap::new(),
+                    cancelled: 0,
+                }),
+                condvar: Condvar::new(),
+            }
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn register(&self, deadline: Instant, slot: Slot) {
+        self.lock().entries.push(Entry { deadline, slot });
+        self.condvar.notify_one();
+    }
+
+    /// Records that the `Sleep` of an entry was dropped, and removes the
+    /// entries of dropped `Sleep`s once they make up half of the heap.
+    fn cancel(&self, slot: Slot) {
+        let mut state = self.lock();
+        // The timer thread only fires entries while holding the state lock.
+        if lock(&slot).fired {
+            return;
+        }
+        drop(slot);
+        state.cancelled += 1;
+        if state.cancelled * 2 >= state.entries.len() {
+            // The heap holds the only reference to slots of dropped `Sleep`s.
+            state
+                .entries
+                .retain(|entry| Arc::strong_count(&entry.slot) > 1);
+            state.cancelled = 0;
+        }
+    }
+
+    fn run(&self) {
+        let mut state = self.lock();
+        loop {
+            let now = Instant::now();
+            while state
+                .entries
+                .peek()
+                .is_some_and(|entry| entry.deadline <= now)
+            {
+                let entry = state.entries.pop().expect("entry was peeked");
+                if Arc::strong_count(&entry.slot) == 1 {
+                    state.cancelled = state.cancelled.saturating_sub(1);
+                    continue;
+                }
+                let waker = {
+                    let mut waiter = lock(&entry.slot);
+                    waiter.fired = true;
+                    waiter.waker.take()
+                };
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+            }
+
+            state = match state.entries.peek() {
+                Some(entry) => {
+                    let timeout = entry.deadline.saturating_duration_since(now);
+                    self.condvar
+                        .wait_timeout(state, timeout)
+                        .unwrap_or_else(|err| err.into_inner())
+                        .0
+                }
+                None => self
+                    .condvar
+                    .wait(state)
+                    .unwrap_or_else(|err| err.into_inner()),
+            };
+        }
+    }
+}
+
+fn lock(slot: &Slot) -> MutexGuard<'_, Waiter> {
+    slot.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// A registered deadline, ordered so the earliest deadline is popped first.
+struct Entry {
+    deadline: Instant,
+    slot: Slot,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.deadline.cmp(&self.deadline)
+    }
+}
+
+/// A future which resolves once its deadline has been reached.
+pub(super) struct Sleep {
+    deadline: Instant,
+    slot: Option<Slot>,
+}
+
+impl Sleep {
+    pub(super) fn new(deadline: Instant) -> Self {
+        Self {
+            deadline,
+            slot: None,
+        }
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+
+        match &self.slot {
+            Some(slot) => lock(slot).waker = Some(cx.waker().clone()),
+            None => {
+                let slot = Arc::new(Mutex::new(Waiter {
+                    waker: Some(cx.waker().clone()),
+                    fired: false,
+                }));
+                Timer::get().register(self.deadline, slot.clone());
+                self.slot = Some(slot);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let Some(slot) = self.slot.take() {
+            // Release the waker right away, even if the entry isn't removed yet.
+            lock(&slot).waker.take();
+            Timer::get().cancel(slot);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use core::future::Future;
+
+/// Fallible conversions from a borrowed value.
+///
+/// Unlike [`TryFrom`](crate::TryFrom), the input isn't consumed, and may be
+/// unsized. This makes it possible to convert from request bodies, `str`
+/// slices, or other borrowed data without cloning it first. The returned
+/// future may borrow from the input.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{TryConvertRefExt, TryFromRef};
+/// # futures_lite::future::block_on(async {
+///
+/// struct Request {
+///     body: Vec<u8>,
+/// }
+///
+/// struct Greeting(String);
+///
+/// impl TryFromRef<Request> for Greeting {
+///     type Error = std::str::Utf8Error;
+///
+///     async fn try_from_ref(req: &Request) -> Result<Self, Self::Error> {
+///         // pretend we're doing async IO here instead.
+///         let body = std::str::from_utf8(&req.body)?;
+///         Ok(Greeting(format!("hello, {}", body)))
+///     }
+/// }
+///
+/// let req = Request { body: b"chashu".to_vec() };
+/// let greeting: Greeting = req.try_convert_ref().await?;
+/// assert_eq!(greeting.0, "hello, chashu");
+/// assert_eq!(req.body, b"chashu");
+/// # Ok::<(), std::str::Utf8Error>(()) });
+/// ```
+pub trait TryFromRef<T: ?Sized>: Sized {
+    /// The type returned in the event of a conversion error.
+    type Error;
+
+    /// Performs the conversion.
+    fn try_from_ref(value: &T) -> impl Future<Output = Result<Self, Self::Error>> + Send;
+}
+
+/// Extension methods for [`TryFromRef`].
+pub trait TryConvertRefExt {
+    /// Converts a reference to `self` into `U`.
+    fn try_convert_ref<U>(&self) -> impl Future<Output = Result<U, U::Error>> + Send
+    where
+        U: TryFromRef<Self>;
+}
+
+impl<T: ?Sized> TryConvertRefExt for T {
+    fn try_convert_ref<U>(&self) -> impl Future<Output = Result<U, U::Error>> + Send
+    where
+        U: TryFromRef<Self>,
+    {
+        U::try_from_ref(self)
+    }
+}
@@ -0,0 +1,91 @@
+use core::future::Future;
+
+use crate::TryFrom;
+
+/// Fallible conversions which need access to a context, such as a database
+/// pool, an HTTP client, or a cache. It is the reciprocal of [`TryIntoWith`].
+///
+/// Every [`TryFrom`] implementation is also a `TryFromWith` implementation for
+/// any context, which is ignored. This makes it possible to require
+/// `TryFromWith` bounds, and accept both kinds of conversions.
+///
+/// # Examples
+///
+/// ```
+/// use async_convert::{TryFromWith, TryIntoWith};
+/// use std::collections::HashMap;
+/// # futures_lite::future::block_on(async {
+///
+/// struct Db {
+///     users: HashMap<u64, String>,
+/// }
+///
+/// struct User {
+///     name: String,
+/// }
+///
+/// impl TryFromWith<u64, Db> for User {
+///     type Error = &'static str;
+///
+///     async fn try_from_with(id: u64, db: &Db) -> Result<Self, Self::Error> {
+///         // pretend we're querying a database here instead.
+///         match db.users.get(&id) {
+///             Some(name) => Ok(User { name: name.clone() }),
+///             None => Err("user not found"),
+///         }
+///     }
+/// }
+///
+/// let db = Db { users: HashMap::from([(1, "chashu".to_string())]) };
+/// let user: User = 1.try_into_with(&db).await?;
+/// assert_eq!(user.name, "chashu");
+/// # Ok::<(), &'static str>(()) });
+/// ```
+pub trait TryFromWith<T, Ctx: ?Sized>: Sized {
+    /// The type returned in the event of a conversion error.
+    type Error;
+
+    /// Performs the conversion.
+    fn try_from_with(value: T, ctx: &Ctx)
+        -> impl Future<Output = Result<Self, Self::Error>> + Send;
+}
+
+/// An attempted conversion which consumes `self` and needs access to a
+/// context.
+///
+/// Library authors should usually not directly implement this trait,
+/// but should prefer implementing the [`TryFromWith`] trait, which provides
+/// an equivalent `TryIntoWith` implementation for free.
+pub trait TryIntoWith<T, Ctx: ?Sized>: Sized {
+    /// The type returned in the event of a conversion error.
+    type Error;
+
+    /// Performs the conversion.
+    fn try_into_with(self, ctx: &Ctx) -> impl Future<Output = Result<T, Self::Error>> + Send;
+}
+
+// TryFromWith implies TryIntoWith
+impl<T, U, Ctx> TryIntoWith<U, Ctx> for T
+where
+    U: TryFromWith<T, Ctx>,
+    Ctx: ?Sized,
+{
+    type Error = U::Error;
+
+    fn try_into_with(self, ctx: &Ctx) -> impl Future<Output = Result<U, U::Error>> + Send {
+        U::try_from_with(self, ctx)
+    }
+}
+
+// TryFrom implies TryFromWith for every context
+impl<T, U, Ctx> TryFromWith<T, Ctx> for U
+where
+    U: TryFrom<T>,
+    Ctx: ?Sized,
+{
+    type Error = U::Error;
+
+    fn try_from_with(value: T, _: &Ctx) -> impl Future<Output = Result<U, U::Error>> + Send {
+        U::try_from(value)
+    }
+}
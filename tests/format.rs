@@ -0,0 +1,138 @@
+#![cfg(any(
+    feature = "ciborium",
+    feature = "rmp-serde",
+    feature = "serde_urlencoded",
+    feature = "toml"
+))]
+
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct User {
+    name: String,
+    age: u8,
+}
+
+#[cfg(feature = "ciborium")]
+mod cbor {
+    use async_convert::{Cbor, DecodeError, Limited, TryFrom};
+    use futures_lite::future::block_on;
+
+    use super::User;
+
+    // `{ "name": "chashu", "age": 3 }`
+    const BODY: &[u8] = b"\xa2\x64name\x66chashu\x63age\x03";
+
+    #[test]
+    fn decode() {
+        let Cbor(user) = block_on(Cbor::<User>::try_from(BODY.to_vec())).unwrap();
+        assert_eq!(user.name, "chashu");
+        assert_eq!(user.age, 3);
+    }
+
+    #[test]
+    fn syntax_error() {
+        let res = block_on(Cbor::<User>::try_from(BODY[..8].to_vec()));
+        assert!(matches!(res, Err(DecodeError::Syntax(_))));
+    }
+
+    #[test]
+    fn limit_exceeded() {
+        let res = block_on(Cbor::<User>::try_from(Limited::<_, 8>::new(BODY)));
+        assert!(matches!(res, Err(DecodeError::LimitExceeded(_))));
+    }
+}
+
+#[cfg(feature = "rmp-serde")]
+mod msgpack {
+    use async_convert::{DecodeError, Limited, MsgPack, TryFrom};
+    use futures_lite::future::block_on;
+
+    use super::User;
+
+    // `{ "name": "chashu", "age": 3 }`
+    const BODY: &[u8] = b"\x82\xa4name\xa6chashu\xa3age\x03";
+
+    #[test]
+    fn decode() {
+        let MsgPack(user) = block_on(MsgPack::<User>::try_from(BODY.to_vec())).unwrap();
+        assert_eq!(user.name, "chashu");
+        assert_eq!(user.age, 3);
+    }
+
+    #[test]
+    fn syntax_error() {
+        let res = block_on(MsgPack::<User>::try_from(BODY[..8].to_vec()));
+        assert!(matches!(res, Err(DecodeError::Syntax(_))));
+    }
+
+    #[test]
+    fn limit_exceeded() {
+        let res = block_on(MsgPack::<User>::try_from(Limited::<_, 8>::new(BODY)));
+        assert!(matches!(res, Err(DecodeError::LimitExceeded(_))));
+    }
+}
+
+#[cfg(feature = "toml")]
+mod toml {
+    use async_convert::{DecodeError, Reader, Toml, TryFrom};
+    use futures_lite::future::block_on;
+
+    use super::User;
+
+    #[test]
+    fn decode() {
+        let body = Reader::new(&b"name = \"chashu\"\nage = 3\n"[..]);
+        let Toml(user) = block_on(Toml::<User>::try_from(body)).unwrap();
+        assert_eq!(user.name, "chashu");
+        assert_eq!(user.age, 3);
+    }
+
+    #[test]
+    fn syntax_error() {
+        let body = b"name = \"chashu\"\nage = = 3\n".to_vec();
+        match block_on(Toml::<User>::try_from(body)) {
+            Err(DecodeError::Syntax(err)) => {
+                assert_eq!(err.line(), Some(2));
+                assert_eq!(err.column(), Some(7));
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn limit_exceeded() {
+        let body = Reader::new(&b"name = \"chashu\"\nage = 3\n"[..]).limit(8);
+        let res = block_on(Toml::<User>::try_from(body));
+        assert!(matches!(res, Err(DecodeError::LimitExceeded(_))));
+    }
+}
+
+#[cfg(feature = "serde_urlencoded")]
+mod form {
+    use async_convert::{DecodeError, Form, Reader, TryFrom};
+    use futures_lite::future::block_on;
+
+    use super::User;
+
+    #[test]
+    fn decode() {
+        let body = Reader::new(&b"name=chashu&age=3"[..]);
+        let Form(user) = block_on(Form::<User>::try_from(body)).unwrap();
+        assert_eq!(user.name, "chashu");
+        assert_eq!(user.age, 3);
+    }
+
+    #[test]
+    fn syntax_error() {
+        let res = block_on(Form::<User>::try_from(b"name=chashu&age=old".to_vec()));
+        assert!(matches!(res, Err(DecodeError::Syntax(_))));
+    }
+
+    #[test]
+    fn limit_exceeded() {
+        let body = Reader::new(&b"name=chashu&age=3"[..]).limit(8);
+        let res = block_on(Form::<User>::try_from(body));
+        assert!(matches!(res, Err(DecodeError::LimitExceeded(_))));
+    }
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "serde_json")]
+
+use async_convert::{DecodeError, Json, Limited, Reader, TryFrom};
+use futures_lite::future::block_on;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct User {
+    name: String,
+    age: u8,
+}
+
+#[test]
+fn decode() {
+    let body = Reader::new(&br#"{ "name": "chashu", "age": 3 }"#[..]);
+    let Json(user) = block_on(Json::<User>::try_from(body)).unwrap();
+    assert_eq!(
+        user,
+        User {
+            name: "chashu".into(),
+            age: 3
+        }
+    );
+}
+
+#[test]
+fn syntax_error() {
+    let body = Reader::new(&b"{\n  \"name\": \"chashu\",\n  \"age\": 3,\n}"[..]);
+    match block_on(Json::<User>::try_from(body)) {
+        Err(DecodeError::Syntax(err)) => {
+            assert_eq!(err.line(), Some(4));
+            assert_eq!(err.column(), Some(1));
+        }
+        res => panic!("unexpected result: {:?}", res),
+    }
+
+    let body = Reader::new(&br#"{ "name": "chashu", "age": 300 }"#[..]);
+    assert!(matches!(
+        block_on(Json::<User>::try_from(body)),
+        Err(DecodeError::Syntax(_))
+    ));
+}
+
+#[test]
+fn limit_exceeded() {
+    let body = Limited::<_, 8>::new(&br#"{ "name": "chashu", "age": 3 }"#[..]);
+    match block_on(Json::<User>::try_from(body)) {
+        Err(DecodeError::LimitExceeded(err)) => assert_eq!(err.limit(), 8),
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn encode() {
+    let user = User {
+        name: "chashu".into(),
+        age: 3,
+    };
+    let body = block_on(String::try_from(Json(user))).unwrap();
+    assert_eq!(body, r#"{"name":"chashu","age":3}"#);
+}
+
+#[test]
+fn from_bytes() {
+    let body = br#"{ "name": "chashu", "age": 3 }"#.to_vec();
+    let Json(user) = block_on(Json::<User>::try_from(body)).unwrap();
+    assert_eq!(user.age, 3);
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "futures-io")]
+
+use async_convert::{DecodeError, Decoders, Limited, Negotiated, Reader, TryFromWith};
+use futures_lite::future::block_on;
+
+#[test]
+fn text() {
+    let decoders = Decoders::<u32>::new().text();
+    let body = Reader::new(&b"42"[..]);
+    let res = block_on(Negotiated::try_from_with(("Text/Plain", body), &decoders));
+    assert_eq!(res.unwrap(), Negotiated(42));
+
+    let body = Reader::new(&b"forty-two"[..]);
+    let res = block_on(Negotiated::try_from_with(("text/plain", body), &decoders));
+    assert!(matches!(res, Err(DecodeError::Syntax(_))));
+}
+
+#[test]
+fn unsupported_media_type() {
+    let decoders = Decoders::<String>::new().text();
+    let body = Reader::new(&b"hello"[..]);
+    match block_on(Negotiated::try_from_with(("image/png", body), &decoders)) {
+        Err(DecodeError::UnsupportedMediaType(err)) => assert_eq!(err.content_type(), "image/png"),
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[test]
+fn register() {
+    let decoders = Decoders::new()
+        .text()
+        .register("text/plain", |buf: Vec<u8>| async move { Ok(buf.len()) });
+    let body = Reader::new(&b"hello"[..]);
+    let res = block_on(Negotiated::try_from_with(("text/plain", body), &decoders));
+    assert_eq!(res.unwrap(), Negotiated(5));
+}
+
+#[test]
+fn limit_exceeded() {
+    let decoders = Decoders::<String>::new().text();
+    let body = Limited::<_, 4>::new(&b"hello"[..]);
+    let res = block_on(Negotiated::try_from_with(("text/plain", body), &decoders));
+    assert!(matches!(res, Err(DecodeError::LimitExceeded(_))));
+}
+
+#[cfg(all(feature = "serde_json", feature = "serde_urlencoded"))]
+#[test]
+fn formats() {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct User {
+        name: String,
+    }
+
+    let decoders = Decoders::<User>::new().json().form();
+
+    let body = Reader::new(&br#"{ "name": "chashu" }"#[..]);
+    let json = ("application/json; charset=utf-8", body);
+    let Negotiated(user) = block_on(Negotiated::try_from_with(json, &decoders)).unwrap();
+    assert_eq!(user.name, "chashu");
+
+    let body = Reader::new(&b"name=nori"[..]);
+    let form = ("application/x-www-form-urlencoded", body);
+    let Negotiated(user) = block_on(Negotiated::try_from_with(form, &decoders)).unwrap();
+    assert_eq!(user.name, "nori");
+}
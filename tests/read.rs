@@ -0,0 +1,63 @@
+#![cfg(feature = "futures-io")]
+
+use std::io::ErrorKind;
+
+use async_convert::{LimitExceeded, Limited, Reader, TryFrom};
+use futures_lite::future::block_on;
+use futures_lite::io::Cursor;
+
+#[test]
+fn vec() {
+    let input = vec![7; 20 * 1024];
+    let output = block_on(Vec::<u8>::try_from(Reader::new(Cursor::new(input.clone()))));
+    assert_eq!(output.unwrap(), input);
+}
+
+#[test]
+fn string() {
+    let output = block_on(String::try_from(Reader::new(&b"hello"[..])));
+    assert_eq!(output.unwrap(), "hello");
+
+    let err = block_on(String::try_from(Reader::new(&[0xff, 0xfe][..]))).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn bytes() {
+    let output = block_on(bytes::Bytes::try_from(Reader::new(&b"hello"[..])));
+    assert_eq!(output.unwrap(), "hello");
+}
+
+#[test]
+fn limit() {
+    let output = block_on(Vec::<u8>::try_from(Reader::new(&b"hello"[..]).limit(5)));
+    assert_eq!(output.unwrap(), b"hello");
+
+    let err = block_on(Vec::<u8>::try_from(Reader::new(&b"hello"[..]).limit(4))).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn limit_exceeded() {
+    let err = block_on(Vec::<u8>::try_from(Reader::new(&b"hello"[..]).limit(4))).unwrap_err();
+    let inner = err.get_ref().unwrap().downcast_ref::<LimitExceeded>();
+    assert_eq!(inner, Some(&LimitExceeded::new(4, 5)));
+}
+
+#[test]
+fn limited() {
+    let output = block_on(Vec::<u8>::try_from(Limited::<_, 5>::new(&b"hello"[..])));
+    assert_eq!(output.unwrap(), b"hello");
+
+    let input = Cursor::new(vec![0; 20 * 1024]);
+    let err = block_on(Vec::<u8>::try_from(Limited::<_, 1024>::new(input))).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    let inner = err
+        .get_ref()
+        .unwrap()
+        .downcast_ref::<LimitExceeded>()
+        .unwrap();
+    assert_eq!(inner.limit(), 1024);
+    assert!(inner.read() > 1024);
+}
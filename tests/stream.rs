@@ -0,0 +1,123 @@
+#![cfg(feature = "futures")]
+
+use async_convert::{TryConvertStreamExt, TryFrom};
+use futures_lite::future::{block_on, yield_now};
+use futures_lite::stream::{self, StreamExt};
+
+#[derive(Debug, PartialEq)]
+struct Delayed(u32);
+
+impl TryFrom<u32> for Delayed {
+    type Error = u32;
+
+    async fn try_from(value: u32) -> Result<Self, Self::Error> {
+        // finish earlier items last.
+        for _ in 0..(4 - value) {
+            yield_now().await;
+        }
+        match value {
+            0 => Err(value),
+            _ => Ok(Delayed(value)),
+        }
+    }
+}
+
+#[test]
+fn each() {
+    block_on(async {
+        let out: Vec<_> = stream::iter(vec![0, 1])
+            .try_convert_each::<Delayed>()
+            .collect()
+            .await;
+        assert_eq!(out, vec![Err(0), Ok(Delayed(1))]);
+    });
+}
+
+#[test]
+fn buffered_preserves_order() {
+    block_on(async {
+        let out: Vec<_> = stream::iter(vec![1, 2, 3])
+            .try_convert_buffered::<Delayed>(3)
+            .collect()
+            .await;
+        assert_eq!(out, vec![Ok(Delayed(1)), Ok(Delayed(2)), Ok(Delayed(3))]);
+    });
+}
+
+#[test]
+fn buffer_unordered_yields_as_completed() {
+    block_on(async {
+        let out: Vec<_> = stream::iter(vec![1, 2, 3])
+            .try_convert_buffer_unordered::<Delayed>(3)
+            .collect()
+            .await;
+        assert_eq!(out, vec![Ok(Delayed(3)), Ok(Delayed(2)), Ok(Delayed(1))]);
+    });
+}
+
+#[test]
+fn zero_limit() {
+    block_on(async {
+        let out: Vec<_> = stream::iter(vec![1, 2])
+            .try_convert_buffered::<Delayed>(0)
+            .collect()
+            .await;
+        assert_eq!(out, vec![Ok(Delayed(1)), Ok(Delayed(2))]);
+
+        let out: Vec<_> = stream::iter(vec![1, 2])
+            .try_convert_buffer_unordered::<Delayed>(0)
+            .collect()
+            .await;
+        assert_eq!(out, vec![Ok(Delayed(1)), Ok(Delayed(2))]);
+    });
+}
+
+mod from_stream {
+    use async_convert::{collect, try_collect, FromStream};
+    use futures_lite::future::block_on;
+    use futures_lite::stream;
+    use std::collections::HashMap;
+
+    #[test]
+    fn is_send() {
+        fn assert_send<F: std::future::Future + Send>(_: F) {}
+        fn generic<C: FromStream<u8> + Send>() {
+            assert_send(collect::<C, _>(stream::iter(vec![1_u8])));
+            assert_send(try_collect::<C, _, (), _>(stream::iter(vec![Ok(1_u8)])));
+            assert_send(collect::<Option<C>, _>(stream::iter(vec![Some(1_u8)])));
+        }
+        generic::<Vec<u8>>();
+        assert_send(collect::<HashMap<u8, u8>, _>(stream::iter(vec![(1, 2)])));
+    }
+
+    #[test]
+    fn strings() {
+        block_on(async {
+            let s: String = collect(stream::iter(vec!['a', 'b'])).await;
+            assert_eq!(s, "ab");
+            let s: String = collect(stream::iter(vec!["ab", "cd"])).await;
+            assert_eq!(s, "abcd");
+        });
+    }
+
+    #[test]
+    fn maps() {
+        block_on(async {
+            let map: HashMap<_, _> = collect(stream::iter(vec![("a", 1), ("b", 2)])).await;
+            assert_eq!(map["b"], 2);
+        });
+    }
+
+    #[test]
+    fn results_and_options() {
+        block_on(async {
+            let res: Result<Vec<i32>, ()> = try_collect(stream::iter(vec![Ok(1), Ok(2)])).await;
+            assert_eq!(res, Ok(vec![1, 2]));
+
+            let opt: Option<Vec<i32>> = collect(stream::iter(vec![Some(1), None])).await;
+            assert_eq!(opt, None);
+            let opt: Option<Vec<i32>> = collect(stream::iter(vec![Some(1), Some(2)])).await;
+            assert_eq!(opt, Some(vec![1, 2]));
+        });
+    }
+}
@@ -0,0 +1,652 @@
+use async_convert::{TryFrom, TryInto};
+use futures_lite::future::block_on;
+use std::future::Future;
+
+/// Runs a future on the runtime of the selected backend. With the `tokio`
+/// feature, the blocking pool and timer only work inside a tokio runtime.
+fn block_on_backend<F: Future>(future: F) -> F::Output {
+    #[cfg(feature = "tokio")]
+    let output = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap()
+        .block_on(future);
+    #[cfg(not(feature = "tokio"))]
+    let output = block_on(future);
+    output
+}
+
+#[derive(Debug, PartialEq)]
+struct GreaterThanZero(i32);
+
+impl TryFrom<i32> for GreaterThanZero {
+    type Error = &'static str;
+
+    async fn try_from(value: i32) -> Result<Self, Self::Error> {
+        if value <= 0 {
+            Err("GreaterThanZero only accepts value superior than zero!")
+        } else {
+            Ok(GreaterThanZero(value))
+        }
+    }
+}
+
+#[test]
+fn try_from_and_try_into() {
+    block_on(async {
+        assert_eq!(GreaterThanZero::try_from(1).await, Ok(GreaterThanZero(1)));
+        let res: Result<GreaterThanZero, _> = 0.try_into().await;
+        assert!(res.is_err());
+    });
+}
+
+#[test]
+fn try_into_is_send() {
+    fn assert_send<F: std::future::Future + Send>(_: F) {}
+    assert_send(TryInto::<GreaterThanZero>::try_into(1));
+}
+
+mod local {
+    use async_convert::{LocalTryFrom, LocalTryInto};
+    use futures_lite::future::block_on;
+    use std::rc::Rc;
+
+    #[derive(Debug, PartialEq)]
+    struct Shared(Rc<i32>);
+
+    impl LocalTryFrom<i32> for Shared {
+        type Error = ();
+
+        async fn try_from(value: i32) -> Result<Self, Self::Error> {
+            let shared = Rc::new(value);
+            futures_lite::future::yield_now().await;
+            Ok(Shared(shared))
+        }
+    }
+
+    #[test]
+    fn local_try_from() {
+        block_on(async {
+            let res: Result<Shared, _> = 2.try_into().await;
+            assert_eq!(res, Ok(Shared(Rc::new(2))));
+        });
+    }
+
+    #[test]
+    fn try_from_is_local_try_from() {
+        block_on(async {
+            let res: Result<super::GreaterThanZero, _> = 2.try_into().await;
+            assert_eq!(res, Ok(super::GreaterThanZero(2)));
+        });
+    }
+}
+
+mod infallible {
+    use async_convert::{From, Into, TryFrom};
+    use futures_lite::future::block_on;
+    use std::convert::Infallible;
+
+    #[derive(Debug, PartialEq)]
+    struct Config {
+        retries: u8,
+    }
+
+    impl From<u8> for Config {
+        async fn from(retries: u8) -> Self {
+            Config { retries }
+        }
+    }
+
+    #[test]
+    fn from_and_into() {
+        block_on(async {
+            assert_eq!(<Config as From<u8>>::from(3).await, Config { retries: 3 });
+            let config: Config = Into::into(4u8).await;
+            assert_eq!(config, Config { retries: 4 });
+        });
+    }
+
+    #[test]
+    fn from_is_try_from() {
+        block_on(async {
+            let res: Result<Config, Infallible> = <Config as TryFrom<u8>>::try_from(5).await;
+            assert_eq!(res, Ok(Config { retries: 5 }));
+        });
+    }
+}
+
+mod ready {
+    use async_convert::{Ready, TryFrom, TryInto};
+    use futures_lite::future::block_on;
+    use std::num::TryFromIntError;
+
+    #[test]
+    fn lifts_std_conversions() {
+        block_on(async {
+            let res = Ready::<u8>::try_from(Ready(12_i64)).await;
+            assert_eq!(res, Ok(Ready(12)));
+            let res = Ready::<u8>::try_from(Ready(-1_i64)).await;
+            assert!(res.is_err());
+        });
+    }
+
+    #[test]
+    fn try_into() {
+        block_on(async {
+            let res: Result<Ready<u8>, TryFromIntError> = Ready(300_i64).try_into().await;
+            assert!(res.is_err());
+            let res: Result<Ready<u8>, TryFromIntError> = Ready(255_i64).try_into().await;
+            assert_eq!(res.map(Ready::into_inner), Ok(255));
+        });
+    }
+}
+
+mod blocking {
+    use super::block_on_backend as block_on;
+    use async_convert::{Batch, Blocking, TryFrom};
+    use std::thread::{self, ThreadId};
+    use std::time::{Duration, Instant};
+
+    struct RanOn(ThreadId);
+
+    struct Slow;
+
+    impl std::convert::TryFrom<u64> for Slow {
+        type Error = &'static str;
+
+        fn try_from(millis: u64) -> Result<Self, Self::Error> {
+            thread::sleep(Duration::from_millis(millis));
+            Ok(Slow)
+        }
+    }
+
+    impl std::convert::TryFrom<u8> for RanOn {
+        type Error = &'static str;
+
+        fn try_from(value: u8) -> Result<Self, Self::Error> {
+            match value {
+                0 => Err("zero"),
+                _ => Ok(RanOn(thread::current().id())),
+            }
+        }
+    }
+
+    #[test]
+    fn runs_conversion_off_the_executor() {
+        block_on(async {
+            let Blocking(ran_on) = Blocking::<RanOn>::try_from(Blocking(1)).await.unwrap();
+            assert_ne!(ran_on.0, thread::current().id());
+
+            let res = Blocking::<RanOn>::try_from(Blocking(0)).await;
+            assert_eq!(res.err(), Some("zero"));
+        });
+    }
+
+    #[test]
+    fn runs_conversions_in_parallel() {
+        block_on(async {
+            // leave an idle thread behind, which shouldn't take every job.
+            Blocking::<Slow>::try_from(Blocking(0)).await.unwrap();
+            thread::sleep(Duration::from_millis(50));
+
+            let start = Instant::now();
+            let batch = Batch::new(vec![Blocking(200); 8]);
+            let converted = batch.try_convert::<Blocking<Slow>>().await.unwrap();
+            assert_eq!(converted.len(), 8);
+            assert!(start.elapsed() < Duration::from_millis(800));
+        });
+    }
+}
+
+mod ext {
+    use super::GreaterThanZero;
+    use async_convert::{TryFrom, TryIntoExt};
+    use futures_lite::future::block_on;
+
+    #[derive(Debug, PartialEq)]
+    struct Small(u8);
+
+    impl TryFrom<GreaterThanZero> for Small {
+        type Error = &'static str;
+
+        async fn try_from(value: GreaterThanZero) -> Result<Self, Self::Error> {
+            std::convert::TryFrom::try_from(value.0)
+                .map(Small)
+                .map_err(|_| "too large")
+        }
+    }
+
+    #[test]
+    fn and_then() {
+        block_on(async {
+            let res = 3.try_convert::<GreaterThanZero>().and_then::<Small>().await;
+            assert_eq!(res, Ok(Small(3)));
+            let res = 300
+                .try_convert::<GreaterThanZero>()
+                .and_then::<Small>()
+                .await;
+            assert_eq!(res, Err("too large"));
+        });
+    }
+
+    #[test]
+    fn or_else() {
+        block_on(async {
+            let res = (-1)
+                .try_convert::<GreaterThanZero>()
+                .or_else(|_| async { Ok::<_, ()>(GreaterThanZero(1)) })
+                .await;
+            assert_eq!(res, Ok(GreaterThanZero(1)));
+        });
+    }
+
+    #[test]
+    fn combinators_are_send() {
+        fn assert_send<F: std::future::Future + Send>(_: F) {}
+        assert_send(
+            1.try_convert::<GreaterThanZero>()
+                .map(|n| n.0)
+                .map_err(|_| ()),
+        );
+    }
+}
+
+mod timeout {
+    use super::block_on_backend as block_on;
+    use async_convert::{TimeoutError, TryFrom, TryIntoExt};
+    use futures_lite::future::pending;
+    use std::time::{Duration, Instant};
+
+    struct Never;
+
+    impl TryFrom<()> for Never {
+        type Error = ();
+
+        async fn try_from(_: ()) -> Result<Self, Self::Error> {
+            pending().await
+        }
+    }
+
+    #[test]
+    fn completes_in_time() {
+        block_on(async {
+            let res = 1
+                .try_into_within::<super::GreaterThanZero>(Duration::from_secs(10))
+                .await;
+            assert!(res.is_ok());
+            let res = 0
+                .try_into_within::<super::GreaterThanZero>(Duration::from_secs(10))
+                .await;
+            assert!(matches!(res, Err(TimeoutError::Conversion(_))));
+        });
+    }
+
+    #[test]
+    fn times_out() {
+        block_on(async {
+            let res = ().try_into_within::<Never>(Duration::from_millis(10)).await;
+            assert!(matches!(res, Err(TimeoutError::Elapsed)));
+            let deadline = Instant::now() + Duration::from_millis(10);
+            let res = ().try_into_until::<Never>(deadline).await;
+            assert!(matches!(res, Err(TimeoutError::Elapsed)));
+        });
+    }
+
+    #[test]
+    fn accepts_any_duration() {
+        block_on(async {
+            let res = 1
+                .try_into_within::<super::GreaterThanZero>(Duration::MAX)
+                .await;
+            assert!(res.is_ok());
+        });
+    }
+
+    // async-io, which async-std's timer builds on, releases the wakers of
+    // dropped timers lazily.
+    #[cfg(any(feature = "tokio", not(feature = "async-std")))]
+    #[test]
+    fn releases_waker_when_dropped() {
+        use std::future::Future;
+        use std::sync::Arc;
+        use std::task::{Context, Wake, Waker};
+
+        struct Noop;
+
+        impl Wake for Noop {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        block_on(async {
+            let noop = Arc::new(Noop);
+            let waker = Waker::from(noop.clone());
+            let mut cx = Context::from_waker(&waker);
+            for _ in 0..100 {
+                let conversion = ().try_into_within::<Never>(Duration::from_secs(3600));
+                let mut conversion = Box::pin(conversion);
+                assert!(conversion.as_mut().poll(&mut cx).is_pending());
+            }
+            drop(waker);
+            assert_eq!(Arc::strong_count(&noop), 1);
+        });
+    }
+}
+
+mod retry {
+    use super::block_on_backend as block_on;
+    use async_convert::{Retry, TryFrom};
+    use futures_lite::future::poll_once;
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    struct Flaky;
+
+    impl TryFrom<u32> for Flaky {
+        type Error = u32;
+
+        async fn try_from(attempt: u32) -> Result<Self, Self::Error> {
+            Err(attempt)
+        }
+    }
+
+    #[test]
+    fn stops_after_max_attempts() {
+        block_on(async {
+            let attempts = Cell::new(0);
+            let res = Retry::new()
+                .max_attempts(3)
+                .backoff(Duration::from_millis(1), Duration::from_millis(2))
+                .try_from_with::<_, Flaky, _>(|| {
+                    attempts.set(attempts.get() + 1);
+                    attempts.get()
+                })
+                .await;
+            assert_eq!(res.err(), Some(3));
+        });
+    }
+
+    #[test]
+    fn skips_non_retryable_errors() {
+        block_on(async {
+            let res = Retry::new()
+                .backoff(Duration::from_millis(1), Duration::from_millis(2))
+                .retry_if(|attempt| *attempt != 7)
+                .try_from::<_, Flaky>(7)
+                .await;
+            assert_eq!(res.err(), Some(7));
+        });
+    }
+
+    #[test]
+    fn accepts_any_backoff() {
+        block_on(async {
+            let retry = Retry::new().backoff(Duration::MAX, Duration::MAX);
+            let res = poll_once(retry.try_from::<_, Flaky>(1)).await;
+            assert!(res.is_none());
+        });
+    }
+}
+
+mod batch {
+    use super::GreaterThanZero;
+    use async_convert::{Batch, TryFrom};
+    use futures_lite::future::{block_on, poll_fn, yield_now};
+    use std::collections::{BTreeMap, HashMap, VecDeque};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::task::{Poll, Waker};
+
+    static RUNNING: AtomicUsize = AtomicUsize::new(0);
+    static MAX_RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+    struct Tracked(usize);
+
+    impl TryFrom<usize> for Tracked {
+        type Error = ();
+
+        async fn try_from(value: usize) -> Result<Self, Self::Error> {
+            let running = RUNNING.fetch_add(1, Ordering::SeqCst) + 1;
+            MAX_RUNNING.fetch_max(running, Ordering::SeqCst);
+            // finish later items first, to check that order is preserved.
+            for _ in 0..(10 - value) {
+                yield_now().await;
+            }
+            RUNNING.fetch_sub(1, Ordering::SeqCst);
+            Ok(Tracked(value))
+        }
+    }
+
+    #[test]
+    fn preserves_order_and_limit() {
+        block_on(async {
+            let items: Vec<usize> = (0..10).collect();
+            let out: Vec<Tracked> = Batch::new(items).limit(3).try_convert().await.unwrap();
+            let out: Vec<usize> = out.into_iter().map(|t| t.0).collect();
+            assert_eq!(out, (0..10).collect::<Vec<_>>());
+            assert_eq!(MAX_RUNNING.load(Ordering::SeqCst), 3);
+        });
+    }
+
+    static POLLS: AtomicUsize = AtomicUsize::new(0);
+    static DONE: AtomicBool = AtomicBool::new(false);
+    static WAITING: Mutex<Vec<Waker>> = Mutex::new(Vec::new());
+
+    struct Waiting;
+
+    impl TryFrom<bool> for Waiting {
+        type Error = ();
+
+        async fn try_from(leader: bool) -> Result<Self, Self::Error> {
+            if leader {
+                for _ in 0..100 {
+                    yield_now().await;
+                }
+                DONE.store(true, Ordering::SeqCst);
+                WAITING.lock().unwrap().drain(..).for_each(Waker::wake);
+                return Ok(Waiting);
+            }
+            // wait until woken by the leader, counting every poll.
+            poll_fn(|cx| {
+                POLLS.fetch_add(1, Ordering::SeqCst);
+                if DONE.load(Ordering::SeqCst) {
+                    return Poll::Ready(Ok(Waiting));
+                }
+                WAITING.lock().unwrap().push(cx.waker().clone());
+                Poll::Pending
+            })
+            .await
+        }
+    }
+
+    #[test]
+    fn polls_only_woken_conversions() {
+        block_on(async {
+            let items = [vec![true], vec![false; 9]].concat();
+            let out: Vec<Waiting> = Batch::new(items).try_convert().await.unwrap();
+            assert_eq!(out.len(), 10);
+            assert_eq!(POLLS.load(Ordering::SeqCst), 18);
+        });
+    }
+
+    #[test]
+    fn is_send() {
+        fn assert_send<F: std::future::Future + Send>(_: F) {}
+        assert_send(Batch::new(vec![1]).try_convert::<GreaterThanZero>());
+    }
+
+    #[test]
+    fn fails_fast() {
+        block_on(async {
+            let res = Batch::new(vec![1, 2, 0, 3])
+                .try_convert::<GreaterThanZero>()
+                .await;
+            assert!(res.is_err());
+        });
+    }
+
+    #[test]
+    fn collections() {
+        block_on(async {
+            let deque: VecDeque<GreaterThanZero> = Batch::new(VecDeque::from(vec![1, 2]))
+                .try_convert()
+                .await
+                .unwrap();
+            assert_eq!(
+                deque,
+                VecDeque::from(vec![GreaterThanZero(1), GreaterThanZero(2)])
+            );
+
+            let map = HashMap::from([("a", 1), ("b", 2)]);
+            let map: HashMap<_, GreaterThanZero> = Batch::new(map).try_convert().await.unwrap();
+            assert_eq!(map["b"], GreaterThanZero(2));
+
+            let map = BTreeMap::from([("a", 1), ("b", 2)]);
+            let map: BTreeMap<_, GreaterThanZero> = Batch::new(map).try_convert().await.unwrap();
+            assert_eq!(map["a"], GreaterThanZero(1));
+        });
+    }
+}
+
+mod partial {
+    use super::GreaterThanZero;
+    use async_convert::{Batch, BatchMode};
+    use futures_lite::future::block_on;
+
+    #[test]
+    fn modes() {
+        block_on(async {
+            let items = vec![1, 0, 2, -1, 3];
+
+            let report = Batch::new(items.clone())
+                .try_convert_partial::<GreaterThanZero>(BatchMode::CollectAll)
+                .await;
+            let indices: Vec<usize> = report.failed().iter().map(|(i, _)| *i).collect();
+            assert_eq!(indices, vec![1, 3]);
+            assert_eq!(report.converted().len(), 3);
+
+            let report = Batch::new(items.clone())
+                .try_convert_partial::<GreaterThanZero>(BatchMode::BestEffort)
+                .await;
+            assert!(report.is_ok());
+            let values = report.into_result().unwrap();
+            assert_eq!(
+                values,
+                vec![GreaterThanZero(1), GreaterThanZero(2), GreaterThanZero(3)]
+            );
+
+            let report = Batch::new(items)
+                .limit(1)
+                .try_convert_partial::<GreaterThanZero>(BatchMode::FailFast)
+                .await;
+            assert_eq!(report.failed().len(), 1);
+            assert_eq!(report.into_converted(), vec![(0, GreaterThanZero(1))]);
+        });
+    }
+}
+
+mod from_str {
+    use async_convert::{FromStr, ParseExt, Parsed, TryFrom};
+    use futures_lite::future::block_on;
+
+    #[derive(Debug, PartialEq)]
+    struct Upper(String);
+
+    impl FromStr for Upper {
+        type Err = ();
+
+        async fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Upper(s.to_uppercase()))
+        }
+    }
+
+    #[test]
+    fn parse_strings() {
+        block_on(async {
+            let owned = String::from("abc");
+            assert_eq!(owned.parse_async().await, Ok(Upper("ABC".into())));
+            let parsed = Parsed::<Upper>::try_from("def").await;
+            assert_eq!(parsed, Ok(Parsed(Upper("DEF".into()))));
+        });
+    }
+}
+
+mod try_from_ref {
+    use async_convert::{TryConvertRefExt, TryFromRef};
+    use futures_lite::future::block_on;
+
+    #[derive(Debug, PartialEq)]
+    struct WordCount(usize);
+
+    impl TryFromRef<str> for WordCount {
+        type Error = &'static str;
+
+        async fn try_from_ref(s: &str) -> Result<Self, Self::Error> {
+            futures_lite::future::yield_now().await;
+            match s.split_whitespace().count() {
+                0 => Err("empty"),
+                n => Ok(WordCount(n)),
+            }
+        }
+    }
+
+    #[test]
+    fn from_unsized() {
+        block_on(async {
+            let text = String::from("hello async world");
+            assert_eq!(text.as_str().try_convert_ref().await, Ok(WordCount(3)));
+            assert_eq!(WordCount::try_from_ref("").await, Err("empty"));
+        });
+    }
+}
+
+mod try_from_with {
+    use super::GreaterThanZero;
+    use async_convert::TryIntoWith;
+    use futures_lite::future::block_on;
+
+    struct Context;
+
+    #[test]
+    fn try_from_ignores_context() {
+        block_on(async {
+            let res: Result<GreaterThanZero, _> = 2.try_into_with(&Context).await;
+            assert_eq!(res, Ok(GreaterThanZero(2)));
+        });
+    }
+}
+
+mod context {
+    use super::GreaterThanZero;
+    use async_convert::{ConversionError, FieldError, TryIntoExt};
+    use futures_lite::future::block_on;
+    use std::error::Error;
+
+    #[test]
+    fn layers() {
+        block_on(async {
+            let err = 0
+                .try_convert::<GreaterThanZero>()
+                .context("parsing the retry count")
+                .context("loading the config")
+                .await
+                .unwrap_err();
+            assert_eq!(err.message(), Some("loading the config"));
+            assert_eq!(err.source_type(), Some("i32"));
+
+            let inner = err.source().unwrap();
+            let inner = inner.downcast_ref::<ConversionError>().unwrap();
+            assert_eq!(inner.message(), Some("parsing the retry count"));
+            assert_eq!(
+                inner.source().unwrap().to_string(),
+                "GreaterThanZero only accepts value superior than zero!"
+            );
+        });
+    }
+
+    #[test]
+    fn from_field_error() {
+        let err = ConversionError::from(FieldError::new("zip", "too short")).with_field("address");
+        assert_eq!(err.field_path(), Some("address.zip"));
+        assert_eq!(err.to_string(), "conversion failed at `address.zip`");
+    }
+}